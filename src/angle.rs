@@ -0,0 +1,323 @@
+//! Angle
+//! 
+//! Degrees, radians, arc-minutes/-seconds, milliarcseconds, and hours (of right ascension/hour angle).
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Neg, Sub}, str::FromStr};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+mod sexagesimal;
+pub use sexagesimal::Sexagesimal;
+use crate::{AstroError, DefoAble, MetricsInternalType, PI, TAU, SpatialUnit, UnitTable, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, parse::{ParseMetricError, UnitRow, parse_metric}, units::UnitFactor};
+
+/// Some angular "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Angle {
+    /// Milliarcseconds.
+    Mas(MetricsInternalType),
+    /// Arcseconds, 1/3600°.
+    ArcSec(MetricsInternalType),
+    /// Arcminutes, 1/60°.
+    ArcMin(MetricsInternalType),
+    /// Degrees.
+    Deg(MetricsInternalType),
+    /// Hours, 15°.
+    Hour(MetricsInternalType),
+    /// Radians (SI).
+    Rad(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Angle]-type.
+pub trait AsAngle {
+    /// self → mas
+    fn mas(&self) -> Angle;
+    /// self → ″
+    fn arcsec(&self) -> Angle;
+    /// self → ′
+    fn arcmin(&self) -> Angle;
+    /// self → °
+    fn deg(&self) -> Angle;
+    /// self → h
+    fn hours(&self) -> Angle;
+    /// self → rad
+    fn rad(&self) -> Angle;
+}
+
+impl UnitTable for Angle {
+    const SI_UNIT: &'static str = "rad";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::Mas(0.0), |_| PI / 648_000_000.0),
+        (Self::ArcSec(0.0), |_| PI / 648_000.0),
+        (Self::ArcMin(0.0), |_| PI / 10_800.0),
+        (Self::Deg(0.0), |_| PI / 180.0),
+        (Self::Hour(0.0), |_| PI / 12.0),
+        (Self::Rad(0.0), |_| 1.0),
+    ];
+}
+
+impl AsAngle for Angle {
+    fn mas(&self) -> Angle { self.to_unit(&Self::Mas(0.0)) }
+    fn arcsec(&self) -> Angle { self.to_unit(&Self::ArcSec(0.0)) }
+    fn arcmin(&self) -> Angle { self.to_unit(&Self::ArcMin(0.0)) }
+    fn deg(&self) -> Angle { self.to_unit(&Self::Deg(0.0)) }
+    fn hours(&self) -> Angle { self.to_unit(&Self::Hour(0.0)) }
+    fn rad(&self) -> Angle { self.to_unit(&Self::Rad(0.0)) }
+}
+
+impl Angle {
+    /// A full turn in `self`'s unit.
+    fn full_turn(&self) -> MetricsInternalType {
+        Self::Rad(TAU).to_unit(self).raw()
+    }
+
+    /// `self` wrapped into `[0, 360°)`, unit retained.
+    pub fn normalized(&self) -> Angle {
+        let turn = self.full_turn();
+        // A tiny negative angle rounds up to a full turn - which is 0 again.
+        let v = self.raw().rem_euclid(turn);
+        let mut a = *self;
+        a.set(if v >= turn { 0.0 } else { v });
+        a
+    }
+
+    /// `self` wrapped into `(-180°, 180°]`, unit retained.
+    pub fn normalized_signed(&self) -> Angle {
+        let turn = self.full_turn();
+        let v = self.raw().rem_euclid(turn);
+        let mut a = *self;
+        a.set(if v > turn / 2.0 { v - turn } else { v });
+        a
+    }
+
+    /// Sine of `self`.
+    pub fn sin(&self) -> MetricsInternalType { self.rad().raw().sin() }
+    /// Cosine of `self`.
+    pub fn cos(&self) -> MetricsInternalType { self.rad().raw().cos() }
+    /// Tangent of `self`.
+    pub fn tan(&self) -> MetricsInternalType { self.rad().raw().tan() }
+
+    /// Four-quadrant arc tangent of `y/x`.
+    pub fn atan2(y: MetricsInternalType, x: MetricsInternalType) -> Angle {
+        Self::Rad(y.atan2(x))
+    }
+
+    /// Arc sine; `x` must be within `[-1, 1]`.
+    pub fn asin(x: MetricsInternalType) -> Result<Angle, AstroError> {
+        match x {
+            _ if (-1.0..=1.0).contains(&x) => Ok(Self::Rad(x.asin())),
+            _ => Err(AstroError::OutOfDomain { what: "asin argument", value: x })
+        }
+    }
+
+    /// Arc cosine; `x` must be within `[-1, 1]`.
+    pub fn acos(x: MetricsInternalType) -> Result<Angle, AstroError> {
+        match x {
+            _ if (-1.0..=1.0).contains(&x) => Ok(Self::Rad(x.acos())),
+            _ => Err(AstroError::OutOfDomain { what: "acos argument", value: x })
+        }
+    }
+
+    /// Small-angle approximation: angle subtended by `size` at `distance`.
+    pub fn subtended(size: SpatialUnit, distance: SpatialUnit) -> Angle {
+        Self::Rad(size / distance)
+    }
+
+    /// Small-angle approximation: linear size of `self` at `distance`, in `distance`'s unit.
+    pub fn size_at(&self, distance: SpatialUnit) -> SpatialUnit {
+        let mut s = distance;
+        s.set(distance.raw() * self.rad().raw());
+        s
+    }
+
+    /// Small-angle approximation: distance at which `size` subtends `self`, in `size`'s unit.
+    pub fn distance_for(&self, size: SpatialUnit) -> SpatialUnit {
+        let mut d = size;
+        d.set(size.raw() / self.rad().raw());
+        d
+    }
+
+    /// `self` as hours, minutes and seconds (of time).
+    pub fn hms(&self) -> Sexagesimal {
+        Sexagesimal::hms(self.hours().raw())
+    }
+
+    /// `self` as degrees, arcminutes and arcseconds.
+    pub fn dms(&self) -> Sexagesimal {
+        Sexagesimal::dms(self.deg().raw())
+    }
+
+    /// From hours, minutes and seconds - sign is taken from `h`.
+    pub fn from_hms(h: MetricsInternalType, m: MetricsInternalType, s: MetricsInternalType) -> Angle {
+        Self::Hour(sexagesimal::combine(h, m, s))
+    }
+
+    /// From degrees, arcminutes and arcseconds - sign is taken from `d`.
+    /// 
+    /// Mind "-0° 30′", for which `-0.0` is needed.
+    pub fn from_dms(d: MetricsInternalType, m: MetricsInternalType, s: MetricsInternalType) -> Angle {
+        Self::Deg(sexagesimal::combine(d, m, s))
+    }
+}
+
+impl DefoAble for Angle {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::Mas(v)    |
+            Self::ArcSec(v) |
+            Self::ArcMin(v) |
+            Self::Deg(v)    |
+            Self::Hour(v)   |
+            Self::Rad(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::Mas(v)    |
+            Self::ArcSec(v) |
+            Self::ArcMin(v) |
+            Self::Deg(v)    |
+            Self::Hour(v)   |
+            Self::Rad(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl Neg for Angle {
+    type Output = Angle;
+    fn neg(self) -> Self::Output {
+        let mut a = self;
+        a.set(-self.raw());
+        a
+    }
+}
+
+impl PartialEq for Angle {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Angle {
+    /// Compares as-is, no wraparound - 359° > 1°.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Angle {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::Mas(_), _) => "mas",
+            (Self::ArcSec(_), Symbols::Unicode) => "″",
+            (Self::ArcSec(_), Symbols::Ascii) => "\"",
+            (Self::ArcMin(_), Symbols::Unicode) => "′",
+            (Self::ArcMin(_), Symbols::Ascii) => "'",
+            (Self::Deg(_), Symbols::Unicode) => "°",
+            (Self::Deg(_), Symbols::Ascii) => "d",
+            (Self::Hour(_), _) => "h",
+            (Self::Rad(_), _) => "rad",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::Rad(_) | Self::Hour(_) => 4,
+            _ => 2
+        }
+    }
+
+    /// °, ′ and ″ (and their ASCII stand-ins) hug the number, the rest doesn't.
+    fn default_spaced(&self) -> bool {
+        matches!(self, Self::Mas(_) | Self::Hour(_) | Self::Rad(_))
+    }
+}
+
+display_via_format!(Angle);
+
+/// Accepted unit symbols.
+const ANGLE_UNITS: &[UnitRow<Angle>] = &[
+    ("mas", Angle::Mas),
+    ("″", Angle::ArcSec), ("\"", Angle::ArcSec), ("arcsec", Angle::ArcSec), ("as", Angle::ArcSec),
+    ("′", Angle::ArcMin), ("'", Angle::ArcMin), ("arcmin", Angle::ArcMin),
+    ("°", Angle::Deg), ("deg", Angle::Deg), ("d", Angle::Deg),
+    ("h", Angle::Hour),
+    ("rad", Angle::Rad),
+];
+
+impl FromStr for Angle {
+    type Err = ParseMetricError;
+    /// Parse e.g. "1.5 deg", "12 mas", "12h 34m 56.7s", "-45° 12′ 3″", "-45:12:03".
+    /// 
+    /// Colon or space separated triplets are taken as degrees.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, ANGLE_UNITS).or_else(|e1| match sexagesimal::parse(s) {
+            Ok(a) => Ok(a),
+            // Report whichever got further along.
+            Err(e2) => Err(if e2.position() > e1.position() { e2 } else { e1 })
+        })
+    }
+}
+
+define_as_for_prim!(AsAngle => Angle [mas => Mas, arcsec => ArcSec, arcmin => ArcMin, deg => Deg, hours => Hour, rad => Rad]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Angle; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+
+#[cfg(test)]
+mod angle_tests {
+    use crate::{ApproxEq, AsSpatialUnit, Tolerance, assert_quantity_eq};
+    use super::*;
+
+    #[test]
+    fn conversions_and_normalization() {
+        assert_eq!(1.hours(), 15.deg());
+        assert_eq!(1.deg(), 3_600_000.mas());
+        assert_quantity_eq!(180.deg(), PI.rad());
+        assert_eq!(10.deg(), 370.deg().normalized());
+        assert_eq!(350.deg(), (-10).deg().normalized());
+        assert_eq!((-10).deg(), 350.deg().normalized_signed());
+        assert_eq!(180.deg(), (-180).deg().normalized_signed());
+        assert_eq!(23.hours(), (-1).hours().normalized());
+    }
+
+    #[test]
+    fn tiny_negatives_normalize_below_a_full_turn() {
+        assert!(matches!((-1e-14).deg().normalized(), Angle::Deg(v) if (0.0..360.0).contains(&v)));
+        assert!(matches!(Angle::Rad(-1e-17).normalized(), Angle::Rad(v) if (0.0..TAU).contains(&v)));
+        assert!(matches!((-1e-16).hours().normalized(), Angle::Hour(v) if (0.0..24.0).contains(&v)));
+        assert!(matches!((-1e-14).deg().normalized_signed(), Angle::Deg(v) if v <= 0.0 && v > -1.0));
+    }
+
+    #[test]
+    fn trig() {
+        assert!(30.deg().sin().approx_eq(&0.5, Tolerance::absolute(1e-15)));
+        assert!(Angle::acos(2.0).is_err());
+        assert_quantity_eq!(45.deg(), Angle::atan2(1.0, 1.0));
+    }
+
+    #[test]
+    fn small_angles() {
+        // 1 au at 1 pc subtends 1″, by definition.
+        assert_quantity_eq!(1.arcsec(), Angle::subtended(1.au(), 1.pc()), Tolerance::relative(1e-12));
+        assert_quantity_eq!(1.au(), 1.arcsec().size_at(1.pc()), Tolerance::relative(1e-12));
+        assert_quantity_eq!(1.pc(), 1.arcsec().distance_for(1.au()), Tolerance::relative(1e-12));
+    }
+
+    #[test]
+    fn parsing_and_display() {
+        assert_eq!(Ok(1.5.deg()), "1.5 deg".parse());
+        assert_eq!(Ok(12.mas()), "12mas".parse());
+        assert_quantity_eq!(Angle::from_hms(12.0, 34.0, 56.7), "12h 34m 56.7s".parse::<Angle>().unwrap());
+        assert_quantity_eq!(Angle::from_dms(-45.0, 12.0, 3.0), "-45° 12′ 3″".parse::<Angle>().unwrap());
+        assert_quantity_eq!(Angle::from_dms(-0.0, 30.0, 0.0), "-00:30:00".parse::<Angle>().unwrap());
+        assert_eq!("12.50°", 12.5.deg().to_string());
+        assert_eq!("12.50d", format!("{:#}", 12.5.deg()));
+        assert_eq!(Ok(1.5.arcmin()), format!("{:#}", 1.5.arcmin()).parse());
+        let e = "12h 61m".parse::<Angle>().unwrap_err();
+        assert_eq!(4, e.position());
+    }
+}
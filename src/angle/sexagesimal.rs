@@ -0,0 +1,176 @@
+//! Sexagesimal notation - "12h 34m 56.7s", "-45° 12′ 3″", "-45:12:03".
+use std::fmt::Display;
+
+use crate::{MetricsInternalType, parse::{ParseMetricError, ParseMetricErrorKind, scan_number}};
+use super::Angle;
+
+/// An angle split into base 60 parts, for display.
+/// 
+/// Seconds get 2 (h m s) or 1 (° ′ ″) decimals by default; `{:.3}` etc. overrides that, `{:#}` uses ASCII.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sexagesimal {
+    value: MetricsInternalType,
+    hours: bool,
+}
+
+impl Sexagesimal {
+    pub(super) fn hms(hours: MetricsInternalType) -> Self {
+        Self { value: hours, hours: true }
+    }
+
+    pub(super) fn dms(degrees: MetricsInternalType) -> Self {
+        Self { value: degrees, hours: false }
+    }
+
+    /// Is the angle negative?
+    pub fn is_negative(&self) -> bool {
+        self.value.is_sign_negative()
+    }
+
+    /// Unsigned whole hours/degrees, minutes, and seconds - unrounded.
+    pub fn components(&self) -> (u32, u32, MetricsInternalType) {
+        let v = self.value.abs();
+        let whole = v.trunc();
+        let min = ((v - whole) * 60.0).trunc();
+        let sec = (v - whole) * 3600.0 - min * 60.0;
+        (whole as u32, min as u32, sec)
+    }
+}
+
+/// Decimals of seconds actually rounded to; f64 carries no more than this for any sensible angle.
+const MAX_ROUNDED_DECIMALS: usize = 12;
+
+impl Display for Sexagesimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let p = f.precision().unwrap_or(if self.hours { 2 } else { 1 });
+        // Round once, in integer ticks, so that 59.96″ won't become "60.0″". Digits past what an f64 can
+        // hold are just zero-padded - which also keeps the tick count from overflowing.
+        let pr = p.min(MAX_ROUNDED_DECIMALS);
+        let scale = 10u128.pow(pr as u32);
+        let ticks = (self.value.abs() * 3600.0 * scale as MetricsInternalType).round() as u128;
+        let turn: u128 = if self.hours { 24 } else { 360 };
+        let whole = match ticks / (3600 * scale) {
+            // A normalized angle (e.g. an RA) that rounds up to a full turn is back at 0.
+            w if w == turn && (0.0..turn as MetricsInternalType).contains(&self.value) => 0,
+            w => w
+        };
+        let min = ticks / (60 * scale) % 60;
+        let (secs, frac) = (ticks / scale % 60, ticks % scale);
+        let sign = if self.is_negative() && ticks > 0 { "-" } else { "" };
+        let sec = match p {
+            0 => format!("{secs:02}"),
+            _ => format!("{secs:02}.{frac:0pr$}{}", "0".repeat(p - pr)),
+        };
+        match (self.hours, f.alternate()) {
+            (true, _) => write!(f, "{sign}{whole}h {min:02}m {sec}s"),
+            (false, false) => write!(f, "{sign}{whole}° {min:02}′ {sec}″"),
+            (false, true) => write!(f, "{sign}{whole}d {min:02}' {sec}\""),
+        }
+    }
+}
+
+/// `a + b/60 + c/3600`, signed by `a` (incl. `-0.0`).
+pub(super) fn combine(a: MetricsInternalType, b: MetricsInternalType, c: MetricsInternalType) -> MetricsInternalType {
+    let v = a.abs() + b / 60.0 + c / 3600.0;
+    if a.is_sign_negative() { -v } else { v }
+}
+
+/// Parse up to three base 60 fields. An 'h' marker makes it hours, anything else is degrees.
+pub(super) fn parse(s: &str) -> Result<Angle, ParseMetricError> {
+    let start = s.len() - s.trim_start().len();
+    let body = s.trim();
+    if body.is_empty() {
+        return Err(ParseMetricError::new(ParseMetricErrorKind::Empty, 0));
+    }
+    let err = |kind, pos: usize| ParseMetricError::new(kind, start + pos);
+    let (negative, mut pos) = match body.chars().next() {
+        Some(c @ ('-' | '−')) => (true, c.len_utf8()),
+        Some('+') => (false, 1),
+        _ => (false, 0)
+    };
+    let mut fields: [MetricsInternalType; 3] = [0.0; 3];
+    let mut hours = false;
+    let mut n = 0;
+    while n < 3 {
+        pos += body[pos..].len() - body[pos..].trim_start().len();
+        let rest = &body[pos..];
+        if rest.is_empty() {
+            break;
+        }
+        if rest.starts_with(['+', '-']) {
+            return Err(err(ParseMetricErrorKind::InvalidNumber, pos));
+        }
+        let (v, len) = scan_number(rest).ok_or(err(ParseMetricErrorKind::InvalidNumber, pos))?;
+        if n > 0 && !(0.0..60.0).contains(&v) {
+            return Err(err(ParseMetricErrorKind::InvalidNumber, pos));
+        }
+        fields[n] = v;
+        pos += len;
+        let marker = body[pos..].chars().next();
+        let ok = match (n, marker) {
+            (_, None) => true,
+            (_, Some(c)) if c.is_whitespace() => true,
+            (0 | 1, Some(':')) => true,
+            (0, Some('h')) => { hours = true; true },
+            (0, Some('°' | 'd')) |
+            (1, Some('m' | '′' | '\'')) |
+            (2, Some('s' | '″' | '"')) => true,
+            _ => false
+        };
+        match marker {
+            Some(c) if !ok => return Err(err(ParseMetricErrorKind::UnknownUnit(c.to_string()), pos)),
+            Some(c) if !c.is_whitespace() => pos += c.len_utf8(),
+            _ => ()
+        }
+        n += 1;
+    }
+    if n == 0 {
+        return Err(err(ParseMetricErrorKind::InvalidNumber, pos));
+    }
+    let rest = body[pos..].trim_start();
+    if !rest.is_empty() {
+        return Err(err(ParseMetricErrorKind::UnknownUnit(rest.to_string()), body.len() - rest.len()));
+    }
+    let v = combine(if negative { -fields[0] } else { fields[0] }, fields[1], fields[2]);
+    // -0 needs help…
+    let v = if negative { -v.abs() } else { v };
+    Ok(if hours { Angle::Hour(v) } else { Angle::Deg(v) })
+}
+
+#[cfg(test)]
+mod sexagesimal_tests {
+    use crate::{AsAngle, DefoAble};
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!("12h 34m 56.70s", Angle::from_hms(12.0, 34.0, 56.7).hms().to_string());
+        assert_eq!("-45° 12′ 03.0″", Angle::from_dms(-45.0, 12.0, 3.0).dms().to_string());
+        assert_eq!("-0d 30' 00\"", format!("{:#.0}", (-0.5).deg().dms()));
+        // Carry, not "60.0″".
+        assert_eq!("1° 00′ 00.0″", 0.999_999.deg().dms().to_string());
+        // …and round the clock, not to "24h".
+        let almost = Angle::from_hms(23.0, 59.0, 59.999);
+        assert_eq!("0h 00m 00.00s", almost.hms().to_string());
+        assert_eq!("0h 00m 00.0s", format!("{:.1}", almost.hms()));
+        assert_eq!("23h 59m 59.999s", format!("{:.3}", almost.hms()));
+        assert_eq!("0° 00′ 00.0″", 359.999_999_9.deg().dms().to_string());
+        assert_eq!("360° 00′ 00.0″", 360.deg().dms().to_string());
+        // Silly precision pads rather than overflowing.
+        assert_eq!(format!("12° 30′ 00.{}″", "0".repeat(40)), format!("{:.40}", 12.5.deg().dms()));
+        assert_eq!(format!("1h 00m 00.{}s", "0".repeat(100)), format!("{:.100}", 15.deg().hms()));
+        assert_eq!((12, 34, 56.7), {
+            let (h, m, s) = Angle::from_hms(12.0, 34.0, 56.7).hms().components();
+            (h, m, (s * 1e6).round() / 1e6)
+        });
+    }
+
+    #[test]
+    fn round_trip() {
+        for a in [Angle::from_hms(5.0, 35.0, 17.3), Angle::from_dms(-5.0, 23.0, 28.0), (-0.25).deg()] {
+            let s = format!("{:.4}", a.dms());
+            let b: Angle = s.parse().unwrap();
+            assert!((a.deg().raw() - b.deg().raw()).abs() < 1e-7, "{s}");
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Apparent places
+//! 
+//! Catalogue (ICRS) positions to geocentric apparent places of date: light-time, annual parallax and
+//! annual aberration, followed by IAU 2006 precession and IAU 2000B nutation. The Earth's orbit comes
+//! from a low-precision solar theory (Meeus, ch. 25) taken as barycentric, which is good to a few tens
+//! of mas. Gravitational light deflection, polar motion and diurnal effects are left out.
+use crate::{AsSpatialUnit, AsTime, AstroError, Astrometry, DefoAble, Epoch, Frame, MetricsInternalType, SkyCoord, SpatialUnit, Time, coords::{dot, mul_vec, spherical, transpose, unit_vector}};
+
+type Vec3 = [MetricsInternalType; 3];
+
+/// Half-step used to differentiate the Earth's orbit, in days.
+const ORBIT_STEP_DAYS: MetricsInternalType = 0.5;
+
+/// Heliocentric Earth in the mean ecliptic and equinox of date, au, at `t` Julian centuries (TT) since J2000.0.
+fn earth_ecliptic(t: MetricsInternalType) -> Vec3 {
+    let l0 = 280.466_46 + t * (36_000.769_83 + t * 0.000_303_2);
+    let m = (357.529_11 + t * (35_999.050_29 - t * 0.000_153_7)).to_radians();
+    let e = 0.016_708_634 - t * (0.000_042_037 + t * 0.000_000_126_7);
+    let c = (1.914_602 - t * (0.004_817 + t * 0.000_014)) * m.sin()
+          + (0.019_993 - t * 0.000_101) * (2.0 * m).sin()
+          + 0.000_289 * (3.0 * m).sin();
+    let (lon, nu) = ((l0 + c).to_radians(), m + c.to_radians());
+    let r = 1.000_001_018 * (1.0 - e * e) / (1.0 + e * nu.cos());
+    // The Sun as seen from the Earth, turned around.
+    [-r * lon.cos(), -r * lon.sin(), 0.0]
+}
+
+/// The Earth's position (au) and velocity (au/d) on GCRS axes.
+fn earth_state(epoch: &Epoch) -> (Vec3, Vec3) {
+    let t = epoch.tt().centuries_since_j2000();
+    let dt = ORBIT_STEP_DAYS / 36_525.0;
+    let to_gcrs = transpose(&Frame::ecliptic(*epoch).rotation_from_icrs());
+    let (ahead, behind) = (earth_ecliptic(t + dt), earth_ecliptic(t - dt));
+    let vel = [0, 1, 2].map(|i| (ahead[i] - behind[i]) / (2.0 * ORBIT_STEP_DAYS));
+    (mul_vec(&to_gcrs, earth_ecliptic(t)), mul_vec(&to_gcrs, vel))
+}
+
+/// Unit vector `u` as seen by an observer moving at `beta` (units of c) - special relativity, in full.
+fn aberration(u: Vec3, beta: Vec3) -> Vec3 {
+    let inv_gamma = (1.0 - dot(&beta, &beta)).sqrt();
+    let w = 1.0 + dot(&u, &beta) / (1.0 + inv_gamma);
+    let p = [0, 1, 2].map(|i| inv_gamma * u[i] + w * beta[i]);
+    let n = dot(&p, &p).sqrt();
+    p.map(|c| c / n)
+}
+
+/// Apparent place of a barycentric ICRS position `p` (au) - or of a direction, if `distant`.
+fn apparent_place(p: Vec3, distant: bool, epoch: &Epoch, earth: (Vec3, Vec3)) -> SkyCoord {
+    let (e, v) = earth;
+    let q = match distant {
+        true => p,
+        false => [0, 1, 2].map(|i| p[i] - e[i])
+    };
+    let r = dot(&q, &q).sqrt();
+    let c_au_d = 1.0 / 1.au().light_time().d().raw();
+    let u = aberration(q.map(|c| c / r), v.map(|c| c / c_au_d));
+    let frame = Frame::true_equator(*epoch);
+    let (lon, lat) = spherical(mul_vec(&frame.rotation_from_icrs(), u));
+    let place = SkyCoord::on_sphere(lon, lat, frame);
+    match distant {
+        true => place,
+        false => place.with_distance(SpatialUnit::Au(r))
+    }
+}
+
+impl SkyCoord {
+    /// Geocentric apparent place at `epoch`, in [Frame::TrueEquator] of that date.
+    /// 
+    /// Annual parallax is applied when `self` has a distance, aberration always. A `SkyCoord` doesn't move,
+    /// so light-time changes nothing here - see [Astrometry::apparent] for that.
+    pub fn apparent(&self, epoch: Epoch) -> SkyCoord {
+        let icrs = self.to_frame(Frame::Icrs);
+        let earth = earth_state(&epoch);
+        match icrs.cartesian() {
+            Some(v) => apparent_place(v.au().raw(), false, &epoch, earth),
+            None => apparent_place(unit_vector(icrs.lon(), icrs.lat()), true, &epoch, earth)
+        }
+    }
+}
+
+impl Astrometry {
+    /// Geocentric apparent place at `epoch`, in [Frame::TrueEquator] of that date.
+    /// 
+    /// Catalogue positions already refer to light arriving at the barycentre, so the star is propagated to the
+    /// epoch minus the *extra* light-time from the barycentre to the Earth - the Rømer delay, ±8.3 min at most.
+    pub fn apparent(&self, epoch: Epoch) -> Result<SkyCoord, AstroError> {
+        let earth = earth_state(&epoch);
+        let (p, _) = self.propagate(epoch)?.state()?;
+        let q = [0, 1, 2].map(|i| p[i] - earth.0[i]);
+        let extra = Time::S((dot(&q, &q).sqrt() - dot(&p, &p).sqrt()) * 1.au().light_time().raw());
+        let seen = self.propagate(epoch - extra)?;
+        let (p, _) = seen.state()?;
+        Ok(apparent_place(p, self.distance().is_err(), &epoch, earth))
+    }
+}
+
+#[cfg(test)]
+mod apparent_tests {
+    use crate::{Angle, ApproxEq, AsAngle, AsVelocity, ProperMotion, TimeScale, Tolerance};
+    use super::*;
+
+    #[test]
+    fn theta_persei() {
+        // Meeus, example 23.a - 2028 Nov 13.19 TD.
+        let star = Astrometry::new(Angle::from_hms(2.0, 44.0, 11.986), Angle::from_dms(49.0, 13.0, 42.48), 0.mas(),
+            ProperMotion::new((0.034_25 * 15.0 * 49.228_467.deg().cos()).arcsec(), (-0.0895).arcsec()), 0.km_s(), Epoch::j2000());
+        let epoch = Epoch::from_calendar(2028, 11, 13, 4, 33, 36.0, TimeScale::Tt).unwrap();
+        let expected = SkyCoord::new(Angle::from_hms(2.0, 46.0, 14.390), Angle::from_dms(49.0, 21.0, 7.45), Frame::true_equator(epoch)).unwrap();
+        assert!(star.apparent(epoch).unwrap().separation(&expected) < 0.1.arcsec());
+    }
+
+    #[test]
+    fn aberration_and_parallax() {
+        let epoch = Epoch::from_calendar(2024, 3, 20, 0, 0, 0.0, TimeScale::Utc).unwrap();
+        let pole = SkyCoord::ecliptic(0.deg(), 90.deg(), Epoch::j2000()).unwrap();
+        let frame = Frame::true_equator(epoch);
+        // At the ecliptic pole the aberration circle has the full constant of aberration as its radius.
+        let sep = pole.apparent(epoch).separation(&pole.to_frame(frame)).arcsec().raw();
+        assert!((sep - 20.5).abs() < 0.4, "{sep}");
+        // ... and the parallax circle the parallax.
+        let near = pole.with_distance(1.pc()).apparent(epoch);
+        let sep = near.separation(&pole.apparent(epoch)).arcsec().raw();
+        assert!((sep - 1.0).abs() < 0.02, "{sep}");
+        assert!(near.distance().unwrap().approx_eq(&1.pc(), Tolerance::relative(1e-6)));
+    }
+}
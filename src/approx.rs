@@ -0,0 +1,197 @@
+//! Approximate equality.
+//! 
+//! Chained floating point conversions make `==` brittle - `1.au().ly().au() == 1.au()` may or may not
+//! hold. [ApproxEq] compares across units with absolute, relative and ULP tolerances.
+use crate::{Acceleration, Angle, Area, AsTemperature, DefoAble, Density, Luminosity, Mass, MetricsInternalType, Quantity, SpatialUnit, Temperature, Time, Velocity, Volume};
+
+/// Tolerances for [ApproxEq]. Two values are approx. equal if *any* of the tolerances is met.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Max. absolute difference, in the unit of the left-hand side.
+    pub abs: MetricsInternalType,
+    /// Max. difference relative to the bigger of the two magnitudes.
+    pub rel: MetricsInternalType,
+    /// Max. distance in units in the last place.
+    pub ulps: u64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self { abs: 0.0, rel: 1e-12, ulps: 4 }
+    }
+}
+
+impl Tolerance {
+    /// Absolute tolerance only.
+    pub fn absolute(abs: MetricsInternalType) -> Self {
+        Self { abs, rel: 0.0, ulps: 0 }
+    }
+
+    /// Relative tolerance only.
+    pub fn relative(rel: MetricsInternalType) -> Self {
+        Self { abs: 0.0, rel, ulps: 0 }
+    }
+
+    /// ULP tolerance only.
+    pub fn ulps(ulps: u64) -> Self {
+        Self { abs: 0.0, rel: 0.0, ulps }
+    }
+
+    /// Check raw values `a` and `b` against the tolerances.
+    pub fn check(&self, a: MetricsInternalType, b: MetricsInternalType) -> bool {
+        if a.is_nan() || b.is_nan() {
+            return false;
+        }
+        if a == b {
+            return true;
+        }
+        let diff = (a - b).abs();
+        diff <= self.abs
+            || diff <= self.rel * a.abs().max(b.abs())
+            || ulps_between(a, b).is_some_and(|u| u <= self.ulps)
+    }
+}
+
+/// Distance of `a` and `b` in units in the last place (of `f64`), `None` if either isn't finite.
+#[allow(clippy::unnecessary_cast)]// not so unnecessary with f128…
+fn ulps_between(a: MetricsInternalType, b: MetricsInternalType) -> Option<u64> {
+    if !a.is_finite() || !b.is_finite() {
+        return None;
+    }
+    // Map the bit patterns onto a monotonic integer line.
+    let ordered = |v: f64| {
+        let bits = v.to_bits() as i64;
+        if bits < 0 { i64::MIN - bits } else { bits }
+    };
+    Some(ordered(a as f64).abs_diff(ordered(b as f64)))
+}
+
+/// Approximate equality, across units where applicable.
+pub trait ApproxEq<Rhs = Self> {
+    /// Is `self` ≈ `other` within `tolerance`?
+    fn approx_eq(&self, other: &Rhs, tolerance: Tolerance) -> bool;
+
+    /// Is `self` ≈ `other` within the default [Tolerance]?
+    fn approx_eq_default(&self, other: &Rhs) -> bool {
+        self.approx_eq(other, Tolerance::default())
+    }
+}
+
+impl ApproxEq for MetricsInternalType {
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        tolerance.check(*self, *other)
+    }
+}
+
+/// [ApproxEq] for metrics where `other` gets converted into `self`'s unit.
+macro_rules! approx_via_cnv {
+    ($($metric:ident),+) => {$(
+        impl ApproxEq for $metric {
+            fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+                tolerance.check(self.raw(), other.cnv_into(self).raw())
+            }
+        }
+    )*};
+}
+approx_via_cnv!(Mass, SpatialUnit, Area, Volume, Density, Time, Velocity, Acceleration, Luminosity);
+
+impl ApproxEq for Angle {
+    /// Compared modulo a full turn - `other` is taken as its equivalent nearest to `self`, so 359.9…° ≈ 0°.
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        let diff = (*self - other.cnv_into(self)).normalized_signed().raw();
+        tolerance.check(self.raw(), self.raw() - diff)
+    }
+}
+
+impl ApproxEq for Temperature {
+    /// Compared in Kelvin; [Temperature::X] is never ≈ anything.
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        match (self, other) {
+            (Self::C(a), Self::C(b)) => tolerance.check(*a, *b),
+            _ => tolerance.check(self.k().raw(), other.k().raw())
+        }
+    }
+}
+
+impl ApproxEq for Quantity {
+    /// Differing dimensions are never ≈.
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        self.dimension() == other.dimension() && tolerance.check(self.value(), other.value())
+    }
+}
+
+#[macro_export]
+/// Assert that two metrics are approximately equal (see [ApproxEq]).
+/// 
+/// # Examples
+/// 
+/// ```
+/// use astrometrics::{AsSpatialUnit, assert_quantity_eq, Tolerance};
+/// assert_quantity_eq!(1.au(), 1.au().ly().au());
+/// assert_quantity_eq!(1.au(), 149_597_870.7.m() * 1000.0, Tolerance::relative(1e-9));
+/// ```
+macro_rules! assert_quantity_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_quantity_eq!($left, $right, $crate::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right, $tolerance) {
+            (l, r, t) => if !$crate::ApproxEq::approx_eq(l, r, t) {
+                panic!("assertion `left ≈ right` failed\n  left: {:?}\n right: {:?}\n   tol: {:?}", l, r, t);
+            }
+        }
+    };
+}
+
+#[macro_export]
+/// Assert that two metrics are *not* approximately equal (see [ApproxEq]).
+macro_rules! assert_quantity_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_quantity_ne!($left, $right, $crate::Tolerance::default())
+    };
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        match (&$left, &$right, $tolerance) {
+            (l, r, t) => if $crate::ApproxEq::approx_eq(l, r, t) {
+                panic!("assertion `left ≉ right` failed\n  left: {:?}\n right: {:?}\n   tol: {:?}", l, r, t);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod approx_tests {
+    use crate::{AsMass, AsSpatialUnit, AsTemperature};
+    use super::*;
+
+    #[test]
+    fn across_units() {
+        assert_quantity_eq!(1.au(), 1.au().ly().au());
+        assert_quantity_eq!(1.au().ly(), 1.au());
+        assert_quantity_eq!(1.mo(), 1.mo().mj().me().kg().g());
+        assert_quantity_eq!(0.c(), 273.15.k());
+        assert_quantity_ne!(1.au(), 1.0001.au());
+        assert_quantity_eq!(1.au(), 1.0001.au(), Tolerance::relative(1e-3));
+        assert_quantity_eq!(1.m(), 1.001.m(), Tolerance::absolute(0.01));
+    }
+
+    #[test]
+    fn ulps() {
+        let a: f64 = 1.0;
+        let b = f64::from_bits(a.to_bits() + 3);
+        assert!(a.approx_eq(&b, Tolerance::ulps(3)));
+        assert!(!a.approx_eq(&b, Tolerance::ulps(2)));
+        assert_eq!(Some(2), ulps_between(-f64::from_bits(1), f64::from_bits(1)));
+        assert!(!Temperature::X.approx_eq_default(&Temperature::X));
+    }
+
+    #[test]
+    fn angles_wrap_around() {
+        use crate::AsAngle;
+        assert!(359.999_999_999_999_9.deg().approx_eq(&0.deg(), Tolerance::absolute(1e-9)));
+        assert!(0.deg().approx_eq(&359.999_999_999_999_9.deg(), Tolerance::absolute(1e-9)));
+        assert!((-1e-10).deg().approx_eq(&Angle::Rad(crate::TAU), Tolerance::absolute(1e-9)));
+        assert!(!359.deg().approx_eq(&0.deg(), Tolerance::absolute(1e-9)));
+        assert!(!Angle::Deg(MetricsInternalType::NAN).approx_eq_default(&0.deg()));
+        assert_quantity_eq!(180.deg(), (-180).deg());
+    }
+}
@@ -0,0 +1,204 @@
+//! Checked arithmetic and validation.
+//! 
+//! The plain operators happily produce NaN, ∞, negative masses and the like. These don't.
+use crate::{Acceleration, Angle, AsTemperature, AstroError, DefoAble, Density, Luminosity, Mass, MetricsInternalType, SpatialUnit, Temperature, Time, Velocity};
+
+/// Validation + checked arithmetic for metrics.
+pub trait Checked: DefoAble + Copy {
+    /// Check that `self` is physically sensible.
+    fn validate(&self) -> Result<Self, AstroError>;
+
+    /// `self` as the left-hand operand of checked arithmetic - just [Checked::validate], unless the value
+    /// can't be changed at all.
+    fn checked_operand(&self) -> Result<Self, AstroError> {
+        self.validate()
+    }
+
+    /// `self + rhs`, if both and the result are valid.
+    fn checked_add(&self, rhs: &Self) -> Result<Self, AstroError> {
+        checked_op(self, rhs, |a, b| a + b)
+    }
+
+    /// `self - rhs`, if both and the result are valid.
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, AstroError> {
+        checked_op(self, rhs, |a, b| a - b)
+    }
+
+    /// `self × rhs`, if `self`, `rhs` and the result are valid.
+    fn checked_mul(&self, rhs: MetricsInternalType) -> Result<Self, AstroError> {
+        finite(rhs, "multiplier")?;
+        let mut s = self.checked_operand()?;
+        s.set(self.raw() * rhs);
+        s.validate()
+    }
+
+    /// `self ÷ rhs`, if `self`, `rhs` and the result are valid.
+    fn checked_div(&self, rhs: MetricsInternalType) -> Result<Self, AstroError> {
+        finite(rhs, "divisor")?;
+        if rhs == 0.0 {
+            return Err(AstroError::DivisionByZero);
+        }
+        let mut s = self.checked_operand()?;
+        s.set(self.raw() / rhs);
+        s.validate()
+    }
+}
+
+/// Validate both operands, apply `op` in `lhs`'s unit, validate the result.
+fn checked_op<T: Checked>(lhs: &T, rhs: &T, op: fn(MetricsInternalType, MetricsInternalType) -> MetricsInternalType) -> Result<T, AstroError> {
+    let mut s = lhs.checked_operand()?;
+    let r = rhs.validate()?.cnv_into(lhs);
+    s.set(op(lhs.raw(), r.raw()));
+    s.validate()
+}
+
+/// NaN and ∞ are never valid.
+fn finite(v: MetricsInternalType, metric: &'static str) -> Result<(), AstroError> {
+    match () {
+        _ if v.is_nan() => Err(AstroError::NaN { metric }),
+        _ if v.is_infinite() => Err(AstroError::Infinite { metric }),
+        _ => Ok(())
+    }
+}
+
+impl Checked for Mass {
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Mass")?;
+        if self.raw() < 0.0 {
+            return Err(AstroError::NegativeMass(*self));
+        }
+        Ok(*self)
+    }
+}
+
+impl Checked for SpatialUnit {
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "SpatialUnit")?;
+        if self.raw() < 0.0 {
+            return Err(AstroError::NegativeLength(*self));
+        }
+        Ok(*self)
+    }
+}
+
+impl Checked for Time {
+    /// Durations may well be negative, but they must be finite.
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Time")?;
+        Ok(*self)
+    }
+}
+
+impl Checked for Velocity {
+    /// Velocities are signed (radial ones especially), but finite.
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Velocity")?;
+        Ok(*self)
+    }
+}
+
+impl Checked for Acceleration {
+    /// Accelerations are vector components as often as not, so signed - but finite.
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Acceleration")?;
+        Ok(*self)
+    }
+}
+
+impl Checked for Luminosity {
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Luminosity")?;
+        if self.raw() < 0.0 {
+            return Err(AstroError::OutOfDomain { what: "luminosity", value: self.raw() });
+        }
+        Ok(*self)
+    }
+}
+
+impl Checked for Density {
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Density")?;
+        if self.raw() < 0.0 {
+            return Err(AstroError::OutOfDomain { what: "density", value: self.raw() });
+        }
+        Ok(*self)
+    }
+}
+
+impl Checked for Angle {
+    /// Any finite angle will do; see [Angle::normalized].
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Angle")?;
+        Ok(*self)
+    }
+}
+
+impl Checked for Temperature {
+    /// Note that [Temperature::X] is NaN and thus not valid for arithmetic.
+    fn validate(&self) -> Result<Self, AstroError> {
+        finite(self.raw(), "Temperature")?;
+        let kelvin = match self {
+            Self::C(v) => *v + crate::temperature::K_C_DELTA,
+            _ => self.raw()
+        };
+        if kelvin < 0.0 {
+            return Err(AstroError::BelowAbsoluteZero(*self));
+        }
+        Ok(*self)
+    }
+
+    /// [Temperature::D] and [Temperature::N] are fixed nominal values - nothing to add to or scale.
+    fn checked_operand(&self) -> Result<Self, AstroError> {
+        match self {
+            Self::D | Self::N => Err(AstroError::OutOfDomain { what: "stellar remnant temperature", value: self.raw() }),
+            _ => self.validate()
+        }
+    }
+}
+
+impl Temperature {
+    /// `self` as Kelvin, without the silent clamping of [AsTemperature::k].
+    pub fn checked_k(&self) -> Result<Self, AstroError> {
+        match self {
+            Self::X => Err(AstroError::NaN { metric: "Temperature" }),
+            _ => Ok(self.validate()?.k())
+        }
+    }
+
+    /// `self` as Celsius, without the silent clamping of [AsTemperature::c].
+    pub fn checked_c(&self) -> Result<Self, AstroError> {
+        match self {
+            Self::X => Err(AstroError::NaN { metric: "Temperature" }),
+            _ => Ok(self.validate()?.c())
+        }
+    }
+}
+
+#[cfg(test)]
+mod checked_tests {
+    use crate::{AsMass, AsSpatialUnit};
+    use super::*;
+
+    #[test]
+    fn masses_and_lengths() {
+        assert_eq!(Ok(3.kg()), 1.kg().checked_add(&2000.g()));
+        assert!(matches!(1.kg().checked_sub(&2.kg()), Err(AstroError::NegativeMass(_))));
+        assert!(matches!(1.ly().checked_mul(-1.0), Err(AstroError::NegativeLength(_))));
+        assert_eq!(Err(AstroError::DivisionByZero), 1.ly().checked_div(0.0));
+        assert!(matches!(1.ly().checked_mul(MetricsInternalType::NAN), Err(AstroError::NaN { .. })));
+        assert!(matches!(MetricsInternalType::MAX.kg().checked_mul(10.0), Err(AstroError::Infinite { .. })));
+    }
+
+    #[test]
+    fn temperatures() {
+        assert!(matches!(Temperature::K(-1.0).checked_k(), Err(AstroError::BelowAbsoluteZero(_))));
+        assert!(matches!(Temperature::C(-300.0).validate(), Err(AstroError::BelowAbsoluteZero(_))));
+        assert!(matches!(Temperature::X.checked_add(&Temperature::K(1.0)), Err(AstroError::NaN { .. })));
+        assert_eq!(Ok(Temperature::K(373.15)), Temperature::C(100.0).checked_k());
+        assert!(Temperature::D.validate().is_ok());
+        assert!(matches!(Temperature::D.checked_add(&Temperature::K(1.0)), Err(AstroError::OutOfDomain { .. })));
+        assert!(matches!(Temperature::N.checked_sub(&Temperature::K(1.0)), Err(AstroError::OutOfDomain { .. })));
+        assert!(matches!(Temperature::N.checked_mul(2.0), Err(AstroError::OutOfDomain { .. })));
+        assert_eq!(Ok(Temperature::K(100_001.0)), Temperature::K(1.0).checked_add(&Temperature::D));
+    }
+}
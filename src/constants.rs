@@ -0,0 +1,273 @@
+//! Physical constant sets.
+//! 
+//! Named, versioned bundles of the constants the conversions (and friends) rely on, each constant
+//! with its uncertainty and citation - handy when results need to match a published paper.
+//! 
+//! [ConstantSet::DEFAULT] is what the plain conversions use.
+use std::fmt::Display;
+
+use crate::MetricsInternalType;
+
+/// A constant with its (standard) uncertainty and source.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Constant {
+    /// Value, in SI.
+    pub value: MetricsInternalType,
+    /// Standard uncertainty, in SI. `0.0` for exact/defined values.
+    pub uncertainty: MetricsInternalType,
+    /// SI unit of the value.
+    pub unit: &'static str,
+    /// Where the value comes from.
+    pub citation: &'static str,
+}
+
+impl Constant {
+    /// An exact (defined or nominal) constant.
+    pub const fn exact(value: MetricsInternalType, unit: &'static str, citation: &'static str) -> Self {
+        Self { value, uncertainty: 0.0, unit, citation }
+    }
+
+    /// A measured constant.
+    pub const fn measured(value: MetricsInternalType, uncertainty: MetricsInternalType, unit: &'static str, citation: &'static str) -> Self {
+        Self { value, uncertainty, unit, citation }
+    }
+
+    /// Is the value exact by definition?
+    pub fn is_exact(&self) -> bool {
+        self.uncertainty == 0.0
+    }
+
+    /// Relative standard uncertainty.
+    pub fn relative_uncertainty(&self) -> MetricsInternalType {
+        self.uncertainty / self.value.abs()
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_exact() {
+            write!(f, "{:e} {} (exact) [{}]", self.value, self.unit, self.citation)
+        } else {
+            write!(f, "{:e} ± {:e} {} [{}]", self.value, self.uncertainty, self.unit, self.citation)
+        }
+    }
+}
+
+/// A mass derived from a mass parameter `gm` and the gravitational constant `g`,
+/// uncertainty dominated by (and propagated from) `g`.
+const fn gm_over_g(gm: MetricsInternalType, g: &Constant, citation: &'static str) -> Constant {
+    let value = gm / g.value;
+    Constant::measured(value, value * g.uncertainty / g.value, "kg", citation)
+}
+
+/// A named, versioned bundle of physical constants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConstantSet {
+    /// Name of the set, e.g. `"IAU 2015"`.
+    pub name: &'static str,
+    /// Astronomical unit.
+    pub au: Constant,
+    /// Light-year (Julian year × c).
+    pub ly: Constant,
+    /// Parsec (648000/π au).
+    pub pc: Constant,
+    /// Solar radius.
+    pub r_sun: Constant,
+    /// Earth equatorial radius.
+    pub r_earth: Constant,
+    /// Solar mass.
+    pub m_sun: Constant,
+    /// Jupiter mass.
+    pub m_jup: Constant,
+    /// Earth mass.
+    pub m_earth: Constant,
+    /// Solar luminosity.
+    pub l_sun: Constant,
+    /// Newtonian constant of gravitation.
+    pub g: Constant,
+    /// Solar mass parameter.
+    pub gm_sun: Constant,
+    /// Speed of light in vacuum.
+    pub c: Constant,
+    /// Stefan-Boltzmann constant.
+    pub sigma: Constant,
+    /// Boltzmann constant.
+    pub k_b: Constant,
+}
+
+// Shared bits…
+const C: Constant = Constant::exact(299_792_458.0, "m/s", "SI definition (1983)");
+const LY: Constant = Constant::exact(9_460_730_472_580_800.0, "m", "IAU: Julian year × c");
+const AU_2012: Constant = Constant::exact(149_597_870_700.0, "m", "IAU 2012 Resolution B2");
+const PC_2015: Constant = Constant::exact(3.085677581491367e16, "m", "IAU 2015 Resolution B2: 648000/π au");
+
+const G_2006: Constant = Constant::measured(6.67428e-11, 6.7e-15, "m³/(kg·s²)", "CODATA 2006 (Mohr et al. 2008)");
+const G_2010: Constant = Constant::measured(6.67384e-11, 8.0e-15, "m³/(kg·s²)", "CODATA 2010 (Mohr et al. 2012)");
+const G_2014: Constant = Constant::measured(6.67408e-11, 3.1e-15, "m³/(kg·s²)", "CODATA 2014 (Mohr et al. 2016)");
+const G_2018: Constant = Constant::measured(6.67430e-11, 1.5e-15, "m³/(kg·s²)", "CODATA 2018 (Tiesinga et al. 2021)");
+const G_2022: Constant = Constant::measured(6.67430e-11, 1.5e-15, "m³/(kg·s²)", "CODATA 2022 (Mohr et al. 2025)");
+
+const SIGMA_2014: Constant = Constant::measured(5.670367e-8, 1.3e-13, "W/(m²·K⁴)", "CODATA 2014 (Mohr et al. 2016)");
+const SIGMA_2018: Constant = Constant::exact(5.670374419e-8, "W/(m²·K⁴)", "CODATA 2018, exact since the 2019 SI redefinition");
+const K_B_2014: Constant = Constant::measured(1.38064852e-23, 7.9e-30, "J/K", "CODATA 2014 (Mohr et al. 2016)");
+const K_B_2018: Constant = Constant::exact(1.380649e-23, "J/K", "SI definition (2019)");
+
+// IAU 2009 system of astronomical constants (Luzum et al. 2011, Celest. Mech. Dyn. Astr. 110, 293).
+const GM_SUN_2009: Constant = Constant::measured(1.32712442099e20, 1e10, "m³/s²", "IAU 2009 (Luzum et al. 2011), TDB-compatible");
+const GM_EARTH_2009: MetricsInternalType = 3.986004418e14;
+const SUN_JUP_RATIO_2009: MetricsInternalType = 1047.348644;
+const R_SUN_2008: Constant = Constant::measured(6.95658e8, 1.4e5, "m", "Haberreiter et al. 2008, ApJ 675, L53");
+const R_EARTH_2009: Constant = Constant::measured(6_378_136.6, 0.1, "m", "IAU 2009 (Luzum et al. 2011)");
+const L_SUN_1995: Constant = Constant::measured(3.846e26, 8e23, "W", "Bahcall, Pinsonneault & Wasserburg 1995");
+
+// IAU 2015 Resolution B3 nominal values (Prša et al. 2016, AJ 152, 41).
+const GM_SUN_2015: Constant = Constant::exact(1.3271244e20, "m³/s²", "IAU 2015 Resolution B3, nominal");
+const GM_EARTH_2015: MetricsInternalType = 3.986004e14;
+const GM_JUP_2015: MetricsInternalType = 1.2668653e17;
+const R_SUN_2015: Constant = Constant::exact(695_700_000.0, "m", "IAU 2015 Resolution B3, nominal");
+const R_EARTH_2015: Constant = Constant::exact(6_378_100.0, "m", "IAU 2015 Resolution B3, nominal equatorial");
+const L_SUN_2015: Constant = Constant::exact(3.828e26, "W", "IAU 2015 Resolution B3, nominal");
+
+/// Astronomy from IAU 2015 B2/B3, fundamentals from the given CODATA release.
+const fn codata_on_iau_2015(name: &'static str, g: Constant, sigma: Constant, k_b: Constant) -> ConstantSet {
+    ConstantSet {
+        name,
+        au: AU_2012,
+        ly: LY,
+        pc: PC_2015,
+        r_sun: R_SUN_2015,
+        r_earth: R_EARTH_2015,
+        m_sun: gm_over_g(GM_SUN_2015.value, &g, "IAU 2015 nominal (GM)☉ / G"),
+        m_jup: gm_over_g(GM_JUP_2015, &g, "IAU 2015 nominal (GM)♃ / G"),
+        m_earth: gm_over_g(GM_EARTH_2015, &g, "IAU 2015 nominal (GM)⊕ / G"),
+        l_sun: L_SUN_2015,
+        g,
+        gm_sun: GM_SUN_2015,
+        c: C,
+        sigma,
+        k_b,
+    }
+}
+
+impl ConstantSet {
+    /// IAU 2009 system of astronomical constants, CODATA 2006 fundamentals.
+    pub const IAU_2009: ConstantSet = ConstantSet {
+        name: "IAU 2009",
+        au: Constant::measured(149_597_870_700.0, 3.0, "m", "IAU 2009 (Luzum et al. 2011)"),
+        ly: LY,
+        pc: Constant::measured(3.085677581491367e16, 618_794.4, "m", "648000/π au (IAU 2009 au)"),
+        r_sun: R_SUN_2008,
+        r_earth: R_EARTH_2009,
+        m_sun: gm_over_g(GM_SUN_2009.value, &G_2006, "IAU 2009 GM☉ / G"),
+        m_jup: gm_over_g(GM_SUN_2009.value / SUN_JUP_RATIO_2009, &G_2006, "IAU 2009 GM☉ / (M☉/M♃) / G"),
+        m_earth: gm_over_g(GM_EARTH_2009, &G_2006, "IAU 2009 GM⊕ / G"),
+        l_sun: L_SUN_1995,
+        g: G_2006,
+        gm_sun: GM_SUN_2009,
+        c: C,
+        sigma: Constant::measured(5.670400e-8, 4.0e-13, "W/(m²·K⁴)", "CODATA 2006 (Mohr et al. 2008)"),
+        k_b: Constant::measured(1.3806504e-23, 2.4e-29, "J/K", "CODATA 2006 (Mohr et al. 2008)"),
+    };
+
+    /// IAU 2009 with the IAU 2012 (exact) au, CODATA 2010 fundamentals.
+    pub const IAU_2012: ConstantSet = ConstantSet {
+        name: "IAU 2012",
+        au: AU_2012,
+        ly: LY,
+        pc: Constant::exact(3.085677581491367e16, "m", "648000/π au (IAU 2012 au)"),
+        r_sun: R_SUN_2008,
+        r_earth: R_EARTH_2009,
+        m_sun: gm_over_g(GM_SUN_2009.value, &G_2010, "IAU 2009 GM☉ / G"),
+        m_jup: gm_over_g(GM_SUN_2009.value / SUN_JUP_RATIO_2009, &G_2010, "IAU 2009 GM☉ / (M☉/M♃) / G"),
+        m_earth: gm_over_g(GM_EARTH_2009, &G_2010, "IAU 2009 GM⊕ / G"),
+        l_sun: L_SUN_1995,
+        g: G_2010,
+        gm_sun: GM_SUN_2009,
+        c: C,
+        sigma: Constant::measured(5.670373e-8, 2.1e-13, "W/(m²·K⁴)", "CODATA 2010 (Mohr et al. 2012)"),
+        k_b: Constant::measured(1.3806488e-23, 1.3e-29, "J/K", "CODATA 2010 (Mohr et al. 2012)"),
+    };
+
+    /// IAU 2015 B2/B3 nominal values, CODATA 2014 fundamentals; M☉, M♃ and M⊕ from the B3 nominal
+    /// mass parameters over G.
+    pub const IAU_2015: ConstantSet = codata_on_iau_2015("IAU 2015", G_2014, SIGMA_2014, K_B_2014);
+
+    /// As [ConstantSet::IAU_2015], but with the masses the crate used before it had constant sets:
+    /// - M☉ = 1.98847e30 kg: IAU 2015 nominal (GM)☉ / CODATA 2014 G, rounded to six figures;
+    /// - M♃ = 1.89813e27 kg: NASA NSSDCA Jupiter Fact Sheet (Williams), not the B3 nominal (GM)♃;
+    /// - M⊕ = 5.9722e24 kg: IAU 2009 derived Earth mass (Luzum et al. 2011, table 1), not the B3 nominal (GM)⊕.
+    pub const IAU_2015_MIXED: ConstantSet = ConstantSet {
+        name: "IAU 2015 (mixed)",
+        au: AU_2012,
+        ly: LY,
+        pc: PC_2015,
+        r_sun: R_SUN_2015,
+        r_earth: R_EARTH_2015,
+        m_sun: Constant::measured(1.98847e30, 7e25, "kg", "IAU 2015 nominal (GM)☉ / CODATA 2014 G"),
+        m_jup: Constant::measured(1.89813e27, 1.9e23, "kg", "NASA Jupiter Fact Sheet"),
+        m_earth: Constant::measured(5.9722e24, 6e20, "kg", "IAU 2009 (Luzum et al. 2011)"),
+        l_sun: L_SUN_2015,
+        g: G_2014,
+        gm_sun: GM_SUN_2015,
+        c: C,
+        sigma: SIGMA_2014,
+        k_b: K_B_2014,
+    };
+
+    /// IAU 2015 astronomy, CODATA 2018 fundamentals.
+    pub const CODATA_2018: ConstantSet = codata_on_iau_2015("CODATA 2018", G_2018, SIGMA_2018, K_B_2018);
+
+    /// IAU 2015 astronomy, CODATA 2022 fundamentals.
+    pub const CODATA_2022: ConstantSet = codata_on_iau_2015("CODATA 2022", G_2022, SIGMA_2018, K_B_2018);
+
+    /// The set used by default - [ConstantSet::IAU_2015].
+    /// 
+    /// Note that this puts M☉ at 1.988475e30 kg, M♃ at 1.898187e27 kg and M⊕ at 5.972365e24 kg (the B3 nominal
+    /// GMs over G), where the crate used to have 1.98847e30, 1.89813e27 and 5.9722e24 - see
+    /// [ConstantSet::IAU_2015_MIXED] for those.
+    pub const DEFAULT: ConstantSet = Self::IAU_2015;
+
+    /// All the presets.
+    pub const PRESETS: [&'static ConstantSet; 6] = [
+        &Self::IAU_2009, &Self::IAU_2012, &Self::IAU_2015, &Self::IAU_2015_MIXED, &Self::CODATA_2018, &Self::CODATA_2022,
+    ];
+
+    /// Find a preset by (case-insensitive) name, e.g. `"iau 2015"`.
+    pub fn by_name(name: &str) -> Option<&'static ConstantSet> {
+        Self::PRESETS.into_iter().find(|cs| cs.name.eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl Default for ConstantSet {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod constants_tests {
+    use crate::PI;
+    use super::*;
+
+    #[test]
+    fn presets() {
+        assert_eq!(Some(&ConstantSet::CODATA_2018), ConstantSet::by_name("codata 2018"));
+        assert!(ConstantSet::by_name("IAU 1976").is_none());
+        // The default IAU 2015 set is pure B3 - M♃ and M⊕ come from the nominal GMs; the mixed one keeps the old masses.
+        let pure = ConstantSet::by_name("iau 2015").unwrap();
+        assert_eq!(&ConstantSet::DEFAULT, pure);
+        assert_eq!(GM_JUP_2015 / G_2014.value, pure.m_jup.value);
+        assert_eq!(GM_EARTH_2015 / G_2014.value, pure.m_earth.value);
+        let mixed = ConstantSet::by_name("IAU 2015 (mixed)").unwrap();
+        assert_eq!((1.89813e27, 5.9722e24), (mixed.m_jup.value, mixed.m_earth.value));
+        assert!(ConstantSet::IAU_2012.au.is_exact());
+        assert!(!ConstantSet::IAU_2009.au.is_exact());
+        for cs in ConstantSet::PRESETS {
+            // GM☉ / M☉ should give back G (to within G's own uncertainty).
+            let g = cs.gm_sun.value / cs.m_sun.value;
+            assert!((g / cs.g.value - 1.0).abs() < 3.0 * cs.g.relative_uncertainty().max(cs.m_sun.relative_uncertainty()), "{}", cs.name);
+            // pc is 648000/π au.
+            assert!((cs.pc.value / cs.au.value - 648_000.0 / PI).abs() < 1e-6);
+        }
+    }
+}
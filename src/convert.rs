@@ -0,0 +1,120 @@
+//! Conversions from metrics into primitives.
+//! 
+//! Floats are `From`, integers are `TryFrom` - what happens on overflow, underflow, negative-to-unsigned
+//! and NaN is governed by the crate-wide [OverflowPolicy].
+use std::{fmt::Display, sync::atomic::{AtomicU8, Ordering}};
+
+use crate::MetricsInternalType;
+
+/// What to do when a metric's raw value doesn't fit the target integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Return a [ConversionError]. The default.
+    #[default]
+    Error,
+    /// Clamp into the target's range. NaN still errors, there's nothing to clamp it to.
+    Saturate,
+    /// `log::warn!` and clamp, NaN included (→ 0). Somewhat the legacy behavior.
+    Warn,
+}
+
+static OVERFLOW_POLICY: AtomicU8 = AtomicU8::new(OverflowPolicy::Error as u8);
+
+/// Set the crate-wide [OverflowPolicy].
+pub fn set_overflow_policy(policy: OverflowPolicy) {
+    OVERFLOW_POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Get the crate-wide [OverflowPolicy].
+pub fn overflow_policy() -> OverflowPolicy {
+    match OVERFLOW_POLICY.load(Ordering::Relaxed) {
+        x if x == OverflowPolicy::Saturate as u8 => OverflowPolicy::Saturate,
+        x if x == OverflowPolicy::Warn as u8 => OverflowPolicy::Warn,
+        _ => OverflowPolicy::Error,
+    }
+}
+
+/// Ways a metric → integer conversion can fail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConversionError {
+    /// Too big for the target.
+    Overflow { value: MetricsInternalType, metric: &'static str, target: &'static str },
+    /// Too small (too negative) for the (signed) target.
+    Underflow { value: MetricsInternalType, metric: &'static str, target: &'static str },
+    /// Negative value into an unsigned target.
+    NegativeToUnsigned { value: MetricsInternalType, metric: &'static str, target: &'static str },
+    /// Not-a-number, e.g. [Temperature::X][crate::Temperature::X].
+    NaN { metric: &'static str, target: &'static str },
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overflow { value, metric, target } => write!(f, "{metric} value '{value}' overflows `{target}`"),
+            Self::Underflow { value, metric, target } => write!(f, "{metric} value '{value}' underflows `{target}`"),
+            Self::NegativeToUnsigned { value, metric, target } => write!(f, "negative {metric} value '{value}' can't be an unsigned `{target}`"),
+            Self::NaN { metric, target } => write!(f, "{metric} value is NaN, can't be a `{target}`"),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Check whether `value` fits into an integer of range `min..=max`, per [overflow_policy].
+/// 
+/// `Ok(())` means go ahead with a (saturating) `as` cast.
+pub fn check_int_range(value: MetricsInternalType, min: MetricsInternalType, max: MetricsInternalType, metric: &'static str, target: &'static str) -> Result<(), ConversionError> {
+    check_int_range_with(overflow_policy(), value, min, max, metric, target)
+}
+
+/// [check_int_range] under the given `policy` rather than the crate-wide one.
+pub(crate) fn check_int_range_with(policy: OverflowPolicy, value: MetricsInternalType, min: MetricsInternalType, max: MetricsInternalType, metric: &'static str, target: &'static str) -> Result<(), ConversionError> {
+    let v = value.trunc();
+    let err = match () {
+        _ if value.is_nan() => ConversionError::NaN { metric, target },
+        // `max + 1` is a power of two, thus exact, even where `max` itself isn't representable.
+        _ if v >= max + 1.0 => ConversionError::Overflow { value, metric, target },
+        _ if v < 0.0 && min == 0.0 => ConversionError::NegativeToUnsigned { value, metric, target },
+        _ if v < min => ConversionError::Underflow { value, metric, target },
+        _ => return Ok(())
+    };
+    match policy {
+        OverflowPolicy::Error => Err(err),
+        OverflowPolicy::Saturate => match err {
+            ConversionError::NaN {..} => Err(err),
+            _ => Ok(())
+        },
+        OverflowPolicy::Warn => {
+            log::warn!("{err}; clamping.");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod convert_tests {
+    use crate::{AsMass, DefoAble, Mass, Temperature};
+    use super::*;
+
+    #[test]
+    fn policies() {
+        // `try_from` under the default policy - no test changes the crate-wide one.
+        assert_eq!(OverflowPolicy::Error, overflow_policy());
+        assert_eq!(Ok(200u8), u8::try_from(200.kg()));
+        assert!(matches!(u8::try_from(256.kg()), Err(ConversionError::Overflow {..})));
+        assert!(matches!(u8::try_from((-1).kg()), Err(ConversionError::NegativeToUnsigned {..})));
+        assert!(matches!(i8::try_from((-129).kg()), Err(ConversionError::Underflow {..})));
+        assert!(matches!(u64::try_from(Temperature::X), Err(ConversionError::NaN {..})));
+        assert_eq!(Ok(1u64 << 63), u64::try_from(Mass::Kg(u64::MAX as f64 * 0.5)));
+        assert!(u64::try_from(Mass::Kg(u64::MAX as f64)).is_err());
+        assert_eq!(Ok(i64::MIN), i64::try_from(Mass::Kg(i64::MIN as f64)));
+
+        // The other policies, passed in rather than set globally.
+        let u8_with = |policy, value: MetricsInternalType| check_int_range_with(policy, value, u8::MIN as MetricsInternalType, u8::MAX as MetricsInternalType, "Mass", "u8");
+        assert_eq!(Ok(()), u8_with(OverflowPolicy::Saturate, 1e6.kg().raw()));
+        assert_eq!(Ok(()), u8_with(OverflowPolicy::Saturate, (-5).kg().raw()));
+        assert!(u8_with(OverflowPolicy::Saturate, Temperature::X.raw()).is_err());
+        assert_eq!(Ok(()), u8_with(OverflowPolicy::Warn, Temperature::X.raw()));
+        assert!(u8_with(OverflowPolicy::Error, 1e6.kg().raw()).is_err());
+    }
+}
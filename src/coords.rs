@@ -0,0 +1,220 @@
+//! Celestial coordinates
+//! 
+//! A direction on the sky - and optionally a distance - in one of the [Frame]s.
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+mod frame;
+mod precession;
+mod nutation;
+mod crossmatch;
+pub use frame::{Frame, obliquity};
+pub use crossmatch::{CrossMatch, SkyIndex, cross_match};
+pub use nutation::nutation;
+pub(crate) use frame::{dot, mul_vec, transpose, unit_vector, spherical};
+use crate::{Angle, AsAngle, AstroError, Checked, DefoAble, Epoch, PI, SpatialUnit, SpatialVector};
+
+/// A position on the celestial sphere, with an optional distance.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct SkyCoord {
+    lon: Angle,
+    lat: Angle,
+    distance: Option<SpatialUnit>,
+    frame: Frame,
+}
+
+impl SkyCoord {
+    /// A position at `lon`, `lat` in the given `frame`; longitude is wrapped into [0°, 360°), latitude
+    /// must be within ±90°.
+    pub fn new(lon: Angle, lat: Angle, frame: Frame) -> Result<Self, AstroError> {
+        lon.validate()?;
+        if lat.validate()?.rad().raw().abs() > PI / 2.0 {
+            return Err(AstroError::OutOfDomain { what: "latitude", value: lat.deg().raw() });
+        }
+        Ok(Self::on_sphere(lon, lat, frame))
+    }
+
+    /// As [SkyCoord::new], for angles that are valid by construction, e.g. straight from [spherical].
+    pub(crate) fn on_sphere(lon: Angle, lat: Angle, frame: Frame) -> Self {
+        Self { lon: lon.normalized(), lat, distance: None, frame }
+    }
+
+    /// ICRS right ascension/declination.
+    pub fn icrs(ra: Angle, dec: Angle) -> Result<Self, AstroError> {
+        Self::new(ra, dec, Frame::Icrs)
+    }
+
+    /// Ecliptic longitude/latitude, mean ecliptic and equinox of `epoch`.
+    pub fn ecliptic(lon: Angle, lat: Angle, epoch: Epoch) -> Result<Self, AstroError> {
+        Self::new(lon, lat, Frame::ecliptic(epoch))
+    }
+
+    /// Galactic l/b.
+    pub fn galactic(l: Angle, b: Angle) -> Result<Self, AstroError> {
+        Self::new(l, b, Frame::Galactic)
+    }
+
+    /// Supergalactic SGL/SGB.
+    pub fn supergalactic(sgl: Angle, sgb: Angle) -> Result<Self, AstroError> {
+        Self::new(sgl, sgb, Frame::Supergalactic)
+    }
+
+    /// `self` at `distance`.
+    pub fn with_distance(mut self, distance: SpatialUnit) -> Self {
+        self.distance = Some(distance);
+        self
+    }
+
+    /// Longitude - RA, λ, l, or SGL depending on frame.
+    pub fn lon(&self) -> Angle { self.lon }
+    /// Latitude - Dec, β, b, or SGB depending on frame.
+    pub fn lat(&self) -> Angle { self.lat }
+    /// Distance, if known.
+    pub fn distance(&self) -> Option<SpatialUnit> { self.distance }
+    /// The frame `self` is in.
+    pub fn frame(&self) -> Frame { self.frame }
+
+    /// `self` transformed into `frame`. Angles come out in degrees; distance is kept as-is.
+    pub fn to_frame(&self, frame: Frame) -> SkyCoord {
+        if frame == self.frame {
+            return *self;
+        }
+        let icrs = mul_vec(&transpose(&self.frame.rotation_from_icrs()), unit_vector(self.lon, self.lat));
+        let (lon, lat) = spherical(mul_vec(&frame.rotation_from_icrs(), icrs));
+        SkyCoord { lon, lat, distance: self.distance, frame }
+    }
+
+    /// Cartesian position in `self`'s frame and in the distance's unit, if there is a distance.
+    pub fn cartesian(&self) -> Option<SpatialVector> {
+        Some(SpatialVector::from_spherical(self.lon, self.lat, self.distance?))
+    }
+
+    /// Angular distance to `other` - Vincenty's formula, stable from coincident to antipodal points.
+    pub fn separation(&self, other: &SkyCoord) -> Angle {
+        let other = other.to_frame(self.frame);
+        let dlon = other.lon - self.lon;
+        let (s1, c1, s2, c2) = (self.lat.sin(), self.lat.cos(), other.lat.sin(), other.lat.cos());
+        let (sd, cd) = (dlon.sin(), dlon.cos());
+        let num = (c2 * sd).hypot(c1 * s2 - s1 * c2 * cd);
+        Angle::atan2(num, s1 * s2 + c1 * c2 * cd).deg()
+    }
+
+    /// Position angle of `other` as seen from `self`, from north through east, in [0°, 360°).
+    pub fn position_angle(&self, other: &SkyCoord) -> Angle {
+        let other = other.to_frame(self.frame);
+        let dlon = other.lon - self.lon;
+        let y = dlon.sin() * other.lat.cos();
+        let x = self.lat.cos() * other.lat.sin() - self.lat.sin() * other.lat.cos() * dlon.cos();
+        Angle::atan2(y, x).deg().normalized()
+    }
+
+    /// Straight-line distance to `other`, if both have a distance - in `self`'s distance unit.
+    pub fn distance_3d(&self, other: &SkyCoord) -> Option<SpatialUnit> {
+        let a = self.cartesian()?;
+        let b = other.to_frame(self.frame).cartesian()?;
+        Some(a.distance_to(&b).cnv_into(&self.distance?))
+    }
+
+    /// Position of a Cartesian `vector` given in `frame`.
+    pub fn from_cartesian(vector: SpatialVector, frame: Frame) -> SkyCoord {
+        let (lon, lat, r) = vector.to_spherical();
+        Self::on_sphere(lon, lat, frame).with_distance(r)
+    }
+}
+
+impl Display for SkyCoord {
+    /// E.g. "ICRS (12h 34m 56.70s, -45° 12′ 03.0″)" or "galactic (120.00°, -5.00°) @ 1200.00 pc".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.frame {
+            Frame::Icrs | Frame::MeanEquator { .. } | Frame::TrueEquator { .. } => write!(f, "{} ({}, {})", self.frame.name(), self.lon.hms(), self.lat.dms())?,
+            _ => write!(f, "{} ({}, {})", self.frame.name(), self.lon.deg(), self.lat.deg())?,
+        }
+        match self.distance {
+            Some(d) => write!(f, " @ {d}"),
+            None => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod coords_tests {
+    use crate::{ApproxEq, AsSpatialUnit, DefoAble, MetricsInternalType, Tolerance};
+    use super::*;
+
+    fn close(a: Angle, b: Angle, tol_deg: MetricsInternalType) -> bool {
+        a.deg().approx_eq(&b, Tolerance::absolute(tol_deg))
+    }
+
+    #[test]
+    fn galactic() {
+        // Galactic centre and north pole, in ICRS (ESA 1997).
+        let gc = SkyCoord::galactic(0.deg(), 0.deg()).unwrap().to_frame(Frame::Icrs);
+        assert!(close(266.404_988_7.deg(), gc.lon(), 1e-5) && close((-28.936_174_9).deg(), gc.lat(), 1e-5), "{gc}");
+        let ngp = SkyCoord::icrs(192.859_481_9.deg(), 27.128_251_0.deg()).unwrap().to_frame(Frame::Galactic);
+        assert!(close(90.deg(), ngp.lat(), 1e-5), "{ngp}");
+    }
+
+    #[test]
+    fn ecliptic_and_supergalactic() {
+        // The June solstice point is on the ecliptic at λ = 90°.
+        let eps = obliquity(2000.0);
+        let sol = SkyCoord::new(90.deg(), eps, Frame::MeanEquator { epoch: 2000.0 }).unwrap().to_frame(Frame::Ecliptic { epoch: 2000.0 });
+        assert!(close(90.deg(), sol.lon(), 1e-9) && close(0.deg(), sol.lat(), 1e-9));
+        // The equinox of date runs ahead by the general precession p_A (IAU 2006); the ecliptic itself barely moves.
+        let t = 0.245;
+        let eq = SkyCoord::ecliptic(0.deg(), 0.deg(), Epoch::j2000()).unwrap().to_frame(Frame::Ecliptic { epoch: 2000.0 + 100.0 * t });
+        let p_a = (5_028.796_195 * t + 1.105_434_8 * t * t).arcsec();
+        assert!(close(p_a, eq.lon(), 1e-3 / 3600.0) && eq.lat().arcsec().raw().abs() < 1.5, "{eq}");
+        let sgp = SkyCoord::galactic(47.37.deg(), 6.32.deg()).unwrap().to_frame(Frame::Supergalactic);
+        assert!(close(90.deg(), sgp.lat(), 1e-9));
+        let origin = SkyCoord::galactic(137.37.deg(), 0.deg()).unwrap().to_frame(Frame::Supergalactic);
+        assert!(close(0.deg(), origin.lon(), 1e-9) && close(0.deg(), origin.lat(), 1e-9));
+    }
+
+    #[test]
+    fn round_trips_and_cartesian() {
+        let c = SkyCoord::icrs(10.68458.deg(), 41.26917.deg()).unwrap().with_distance(765_000.pc());
+        for f in [Frame::Ecliptic { epoch: 2024.5 }, Frame::Galactic, Frame::Supergalactic] {
+            let back = c.to_frame(f).to_frame(Frame::Icrs);
+            assert!(close(c.lon(), back.lon(), 1e-10) && close(c.lat(), back.lat(), 1e-10), "{}", f.name());
+        }
+        let v = c.to_frame(Frame::Galactic).cartesian().unwrap();
+        assert!(v.norm().approx_eq(&765_000.pc(), Tolerance::relative(1e-12)));
+        let back = SkyCoord::from_cartesian(v, Frame::Galactic).to_frame(Frame::Icrs);
+        assert!(close(c.lon(), back.lon(), 1e-10) && close(c.lat(), back.lat(), 1e-10));
+        assert!(SkyCoord::icrs(0.deg(), 0.deg()).unwrap().cartesian().is_none());
+        assert_eq!("ICRS (0h 42m 44.30s, 41° 16′ 09.0″) @ 765000.00 pc", c.to_string());
+    }
+
+    #[test]
+    fn latitude_is_checked() {
+        for lat in [91.deg(), (-90.001).deg(), 2.rad()] {
+            assert!(matches!(SkyCoord::icrs(0.deg(), lat), Err(AstroError::OutOfDomain { what: "latitude", .. })), "{lat}");
+        }
+        assert!(SkyCoord::galactic(0.deg(), Angle::Deg(MetricsInternalType::NAN)).is_err());
+        assert!(SkyCoord::icrs(Angle::Deg(MetricsInternalType::INFINITY), 0.deg()).is_err());
+        assert!(SkyCoord::icrs(400.deg(), 90.deg()).is_ok() && SkyCoord::supergalactic(0.deg(), (-90).deg()).is_ok());
+        // A hair below 0° RA is 0h, not 24h.
+        assert_eq!("0h 00m 00.00s", SkyCoord::icrs((-1e-14).deg(), 0.deg()).unwrap().lon().hms().to_string());
+    }
+
+    #[test]
+    fn separation_and_position_angle() {
+        let a = SkyCoord::icrs(10.deg(), 20.deg()).unwrap();
+        assert!(close(1.arcsec(), a.separation(&SkyCoord::icrs(10.deg(), 20.deg() + 1.arcsec()).unwrap()), 1e-12));
+        assert!(close(0.deg(), a.position_angle(&SkyCoord::icrs(10.deg(), 21.deg()).unwrap()), 1e-9));
+        assert!(close(90.deg(), a.position_angle(&SkyCoord::icrs(10.001.deg(), 20.deg()).unwrap()), 1e-3));
+        // Antipodes, and across frames.
+        assert!(close(180.deg(), a.separation(&SkyCoord::icrs(190.deg(), (-20).deg()).unwrap()), 1e-9));
+        assert!(close(0.deg(), a.separation(&a.to_frame(Frame::Galactic)), 1e-9));
+        // Sirius - Betelgeuse: 27.1045° apart, Betelgeuse at PA 331.909°.
+        let sirius = SkyCoord::icrs(Angle::from_hms(6.0, 45.0, 8.917), Angle::from_dms(-16.0, 42.0, 58.02)).unwrap();
+        let betelgeuse = SkyCoord::icrs(Angle::from_hms(5.0, 55.0, 10.305), Angle::from_dms(7.0, 24.0, 25.43)).unwrap();
+        assert!(close(27.104_529.deg(), sirius.separation(&betelgeuse), 1e-6));
+        assert!(close(331.909_187.deg(), sirius.position_angle(&betelgeuse), 1e-6));
+        let d = sirius.with_distance(2.64.pc()).distance_3d(&betelgeuse.with_distance(168.1.pc())).unwrap();
+        assert!(d.pc().raw() > 165.0 && d.pc().raw() < 168.1, "{d}");
+        assert!(sirius.distance_3d(&betelgeuse.with_distance(168.1.pc())).is_none());
+    }
+}
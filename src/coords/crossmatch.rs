@@ -0,0 +1,201 @@
+//! Catalogue cross-matching
+//! 
+//! A k-d tree over unit vectors - chord length is monotonic in angular separation, so plain Euclidean
+//! searches in 3D do the job without any of the pole/wraparound trouble of a lon/lat grid.
+use crate::{Angle, AsAngle, DefoAble, Frame, MetricsInternalType, PI, SpatialUnit};
+
+use super::{SkyCoord, frame::unit_vector};
+
+type Vec3 = [MetricsInternalType; 3];
+
+/// A pairing from [cross_match].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossMatch {
+    /// Index into the left-hand catalogue.
+    pub left: usize,
+    /// Index into the right-hand catalogue.
+    pub right: usize,
+    /// Angular separation of the two.
+    pub separation: Angle,
+    /// Straight-line distance between the two, if both have a distance.
+    pub distance: Option<SpatialUnit>,
+}
+
+/// Spatial index over a set of sky positions.
+#[derive(Debug, Clone)]
+pub struct SkyIndex {
+    /// Positions (ICRS unit vectors) in k-d order - the median of each range splits it.
+    points: Vec<Vec3>,
+    /// Original index of each of `points`.
+    ids: Vec<usize>,
+}
+
+fn chord2(a: &Vec3, b: &Vec3) -> MetricsInternalType {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+/// Chord length between two points `angle` apart on the unit sphere, squared.
+fn angle_to_chord2(angle: Angle) -> MetricsInternalType {
+    let half = Angle::Rad(angle.rad().raw().clamp(0.0, PI) / 2.0);
+    (2.0 * half.sin()).powi(2)
+}
+
+fn chord2_to_angle(c2: MetricsInternalType) -> Angle {
+    Angle::Rad(2.0 * (c2.sqrt() / 2.0).min(1.0).asin()).deg()
+}
+
+impl SkyIndex {
+    /// Index over `coords`, in whatever frames they are.
+    pub fn new(coords: &[SkyCoord]) -> Self {
+        let mut entries: Vec<(Vec3, usize)> = coords.iter().enumerate()
+            .map(|(i, c)| {
+                let c = c.to_frame(Frame::Icrs);
+                (unit_vector(c.lon(), c.lat()), i)
+            })
+            .collect();
+        Self::build(&mut entries, 0);
+        let (points, ids) = entries.into_iter().unzip();
+        Self { points, ids }
+    }
+
+    fn build(entries: &mut [(Vec3, usize)], depth: usize) {
+        if entries.len() <= 1 {
+            return;
+        }
+        let (axis, mid) = (depth % 3, entries.len() / 2);
+        entries.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+        let (lower, upper) = entries.split_at_mut(mid);
+        Self::build(lower, depth + 1);
+        Self::build(&mut upper[1..], depth + 1);
+    }
+
+    /// Number of indexed positions.
+    pub fn len(&self) -> usize { self.points.len() }
+
+    /// Is the index empty?
+    pub fn is_empty(&self) -> bool { self.points.is_empty() }
+
+    /// All indexed positions within `radius` of `target`, as (index, separation), nearest first.
+    pub fn within(&self, target: &SkyCoord, radius: Angle) -> Vec<(usize, Angle)> {
+        let q = Self::query_vector(target);
+        let mut found = vec![];
+        self.visit(0, self.points.len(), 0, &q, angle_to_chord2(radius), &mut found);
+        found.sort_by(|a, b| a.1.total_cmp(&b.1));
+        found.into_iter().map(|(i, c2)| (i, chord2_to_angle(c2))).collect()
+    }
+
+    /// The indexed position nearest to `target`, as (index, separation), if any.
+    pub fn nearest(&self, target: &SkyCoord) -> Option<(usize, Angle)> {
+        let q = Self::query_vector(target);
+        let mut best = None;
+        self.closest(0, self.points.len(), 0, &q, &mut best);
+        best.map(|(i, c2)| (i, chord2_to_angle(c2)))
+    }
+
+    fn query_vector(target: &SkyCoord) -> Vec3 {
+        let t = target.to_frame(Frame::Icrs);
+        unit_vector(t.lon(), t.lat())
+    }
+
+    fn visit(&self, lo: usize, hi: usize, depth: usize, q: &Vec3, r2: MetricsInternalType, found: &mut Vec<(usize, MetricsInternalType)>) {
+        if lo >= hi {
+            return;
+        }
+        let (axis, mid) = (depth % 3, lo + (hi - lo) / 2);
+        let p = &self.points[mid];
+        let d2 = chord2(p, q);
+        if d2 <= r2 {
+            found.push((self.ids[mid], d2));
+        }
+        let diff = q[axis] - p[axis];
+        let (near, far) = if diff < 0.0 { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+        self.visit(near.0, near.1, depth + 1, q, r2, found);
+        if diff * diff <= r2 {
+            self.visit(far.0, far.1, depth + 1, q, r2, found);
+        }
+    }
+
+    fn closest(&self, lo: usize, hi: usize, depth: usize, q: &Vec3, best: &mut Option<(usize, MetricsInternalType)>) {
+        if lo >= hi {
+            return;
+        }
+        let (axis, mid) = (depth % 3, lo + (hi - lo) / 2);
+        let p = &self.points[mid];
+        let d2 = chord2(p, q);
+        if best.is_none_or(|(_, b)| d2 < b) {
+            *best = Some((self.ids[mid], d2));
+        }
+        let diff = q[axis] - p[axis];
+        let (near, far) = if diff < 0.0 { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+        self.closest(near.0, near.1, depth + 1, q, best);
+        if best.is_none_or(|(_, b)| diff * diff < b) {
+            self.closest(far.0, far.1, depth + 1, q, best);
+        }
+    }
+}
+
+/// Pair each of `left` with its nearest neighbour in `right`, if that is within `tolerance`.
+/// 
+/// One-directional: several `left` entries may end up with the same `right` one.
+pub fn cross_match(left: &[SkyCoord], right: &[SkyCoord], tolerance: Angle) -> Vec<CrossMatch> {
+    let index = SkyIndex::new(right);
+    left.iter().enumerate()
+        .filter_map(|(i, a)| {
+            let (j, _) = index.nearest(a)?;
+            let b = &right[j];
+            let separation = a.separation(b);
+            (separation <= tolerance).then(|| CrossMatch { left: i, right: j, separation, distance: a.distance_3d(b) })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod crossmatch_tests {
+    use crate::AsSpatialUnit;
+    use super::*;
+
+    /// A deterministic scatter over the whole sky.
+    fn catalogue(n: usize) -> Vec<SkyCoord> {
+        (0..n).map(|i| {
+            let f = (i as MetricsInternalType + 0.5) / n as MetricsInternalType;
+            SkyCoord::icrs((i as MetricsInternalType * 137.507_764).deg(), (1.0 - 2.0 * f).asin().to_degrees().deg()).unwrap()
+        }).collect()
+    }
+
+    #[test]
+    fn index_agrees_with_brute_force() {
+        let cat = catalogue(2_000);
+        let index = SkyIndex::new(&cat);
+        assert_eq!(2_000, index.len());
+        for target in [SkyCoord::icrs(0.deg(), 90.deg()).unwrap(), SkyCoord::icrs(123.4.deg(), (-45.6).deg()).unwrap(), SkyCoord::galactic(0.deg(), 0.deg()).unwrap()] {
+            let expected: Vec<usize> = (0..cat.len()).filter(|i| cat[*i].separation(&target) <= 5.deg()).collect();
+            let mut got: Vec<usize> = index.within(&target, 5.deg()).into_iter().map(|(i, _)| i).collect();
+            got.sort();
+            assert_eq!(expected, got);
+            let nearest = (0..cat.len()).min_by(|a, b| cat[*a].separation(&target).partial_cmp(&cat[*b].separation(&target)).unwrap());
+            assert_eq!(nearest, index.nearest(&target).map(|(i, _)| i));
+        }
+        assert!(SkyIndex::new(&[]).nearest(&SkyCoord::icrs(0.deg(), 0.deg()).unwrap()).is_none());
+    }
+
+    #[test]
+    fn matching() {
+        let ours = catalogue(500);
+        // Theirs: shuffled, jittered by ≤ 0.5″, with distances, minus a few.
+        let theirs: Vec<SkyCoord> = ours.iter().enumerate().rev()
+            .filter(|(i, _)| i % 50 != 0)
+            .map(|(i, c)| SkyCoord::icrs(c.lon(), c.lat() + (0.25 * (i % 3) as MetricsInternalType).arcsec()).unwrap().with_distance(10.pc()))
+            .collect();
+        let matches = cross_match(&ours, &theirs, 1.arcsec());
+        assert_eq!(490, matches.len());
+        for m in &matches {
+            assert!(m.separation <= 1.arcsec() && m.distance.is_none());
+            assert!(theirs[m.right].separation(&ours[m.left]) < 0.6.arcsec());
+        }
+        // Both with distances: ≤ 0.5″ at 10 pc is ≤ 5 au.
+        let ours: Vec<SkyCoord> = ours.into_iter().map(|c| c.with_distance(10.pc())).collect();
+        let back = cross_match(&theirs, &ours, 1.arcsec());
+        assert_eq!(490, back.len());
+        assert!(back.iter().all(|m| m.distance.unwrap() < 5.1.au()));
+    }
+}
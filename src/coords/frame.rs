@@ -0,0 +1,158 @@
+//! Reference frames and the rotations between them.
+use serde::{Deserialize, Serialize};
+
+use crate::{Angle, AsAngle, Epoch, MetricsInternalType};
+
+use super::precession::{bias_precession, bias_precession_nutation};
+
+/// A 3×3 (rotation) matrix, row-major.
+pub(crate) type Mat3 = [[MetricsInternalType; 3]; 3];
+
+/// Celestial reference frames.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum Frame {
+    /// International Celestial Reference System; RA/Dec.
+    Icrs,
+    /// Mean ecliptic and equinox of the given Julian epoch (TT), e.g. `2000.0` - IAU 2006 obliquity and precession.
+    Ecliptic { epoch: MetricsInternalType },
+    /// Mean equator and equinox of the given Julian epoch (TT), e.g. `2050.0` - IAU 2006 precession, frame bias incl.
+    MeanEquator { epoch: MetricsInternalType },
+    /// True equator and equinox of the given Julian epoch (TT) - as [Frame::MeanEquator], plus IAU 2000B nutation.
+    TrueEquator { epoch: MetricsInternalType },
+    /// IAU 1958 galactic coordinates, as realized by Hipparcos (ESA 1997).
+    Galactic,
+    /// De Vaucouleurs' supergalactic coordinates.
+    Supergalactic,
+}
+
+/// ICRS → galactic (ESA 1997, The Hipparcos Catalogue, vol. 1, §1.5.3).
+#[allow(clippy::excessive_precision)]// digits as published
+const ICRS_TO_GALACTIC: Mat3 = [
+    [-0.054_875_560_416_215_4, -0.873_437_090_234_885_0, -0.483_835_015_548_713_2],
+    [ 0.494_109_427_875_583_7, -0.444_829_629_960_011_2,  0.746_982_244_497_218_9],
+    [-0.867_666_149_019_004_7, -0.198_076_373_431_201_5,  0.455_983_776_175_066_9],
+];
+
+/// Galactic longitude/latitude of the supergalactic north pole, and longitude of its zero point (at b = 0).
+const SGP_L_DEG: MetricsInternalType = 47.37;
+const SGP_B_DEG: MetricsInternalType = 6.32;
+const SG_ORIGIN_L_DEG: MetricsInternalType = 137.37;
+
+/// Mean obliquity of the ecliptic for the given Julian epoch (IAU 2006, Hilton et al. 2006).
+pub fn obliquity(epoch: MetricsInternalType) -> Angle {
+    let t = (epoch - 2000.0) / 100.0;
+    Angle::ArcSec(84_381.406 + t * (-46.836_769 + t * (-0.000_183_1 + t * (0.002_003_40 + t * (-0.000_000_576 + t * -0.000_000_043_4)))))
+}
+
+/// Unit vector towards `lon`, `lat`.
+pub(crate) fn unit_vector(lon: Angle, lat: Angle) -> [MetricsInternalType; 3] {
+    let (cb, sb) = (lat.cos(), lat.sin());
+    [cb * lon.cos(), cb * lon.sin(), sb]
+}
+
+/// `lon` ∈ [0°, 360°) and `lat` of a (not necessarily unit) vector, in degrees.
+pub(crate) fn spherical(v: [MetricsInternalType; 3]) -> (Angle, Angle) {
+    let rho = v[0].hypot(v[1]);
+    (Angle::atan2(v[1], v[0]).deg().normalized(), Angle::atan2(v[2], rho).deg())
+}
+
+pub(crate) fn dot(a: &[MetricsInternalType; 3], b: &[MetricsInternalType; 3]) -> MetricsInternalType {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub(crate) fn mul_vec(m: &Mat3, v: [MetricsInternalType; 3]) -> [MetricsInternalType; 3] {
+    [0, 1, 2].map(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+}
+
+pub(crate) fn transpose(m: &Mat3) -> Mat3 {
+    [0, 1, 2].map(|i| [m[0][i], m[1][i], m[2][i]])
+}
+
+pub(crate) fn mul_mat(a: &Mat3, b: &Mat3) -> Mat3 {
+    [0, 1, 2].map(|i| [0, 1, 2].map(|j| a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j]))
+}
+
+/// Rotation of the frame about the x-axis by `angle`.
+pub(crate) fn rot_x(angle: Angle) -> Mat3 {
+    let (c, s) = (angle.cos(), angle.sin());
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+/// Rotation of the frame about the z-axis by `angle`.
+pub(crate) fn rot_z(angle: Angle) -> Mat3 {
+    let (c, s) = (angle.cos(), angle.sin());
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+/// Galactic → supergalactic; rows are the supergalactic axes in galactic coordinates.
+fn galactic_to_supergalactic() -> Mat3 {
+    let z = unit_vector(SGP_L_DEG.deg(), SGP_B_DEG.deg());
+    let x = unit_vector(SG_ORIGIN_L_DEG.deg(), 0.deg());
+    let y = [z[1] * x[2] - z[2] * x[1], z[2] * x[0] - z[0] * x[2], z[0] * x[1] - z[1] * x[0]];
+    [x, y, z]
+}
+
+impl Frame {
+    /// [Frame::Ecliptic] of `epoch`, whatever its time scale.
+    pub fn ecliptic(epoch: Epoch) -> Self {
+        Self::Ecliptic { epoch: epoch.tt().julian_epoch() }
+    }
+
+    /// [Frame::MeanEquator] of `epoch`, whatever its time scale.
+    pub fn mean_equator(epoch: Epoch) -> Self {
+        Self::MeanEquator { epoch: epoch.tt().julian_epoch() }
+    }
+
+    /// [Frame::TrueEquator] of `epoch`, whatever its time scale.
+    pub fn true_equator(epoch: Epoch) -> Self {
+        Self::TrueEquator { epoch: epoch.tt().julian_epoch() }
+    }
+
+    /// Rotation matrix taking ICRS vectors into `self`.
+    pub(crate) fn rotation_from_icrs(&self) -> Mat3 {
+        match self {
+            Self::Icrs => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+            Self::Ecliptic { epoch } => mul_mat(&rot_x(obliquity(*epoch)), &bias_precession((epoch - 2000.0) / 100.0)),
+            Self::MeanEquator { epoch } => bias_precession((epoch - 2000.0) / 100.0),
+            Self::TrueEquator { epoch } => bias_precession_nutation((epoch - 2000.0) / 100.0),
+            Self::Galactic => ICRS_TO_GALACTIC,
+            Self::Supergalactic => mul_mat(&galactic_to_supergalactic(), &ICRS_TO_GALACTIC),
+        }
+    }
+
+    /// Short name of the frame, e.g. "ICRS".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Icrs => "ICRS",
+            Self::Ecliptic { .. } => "ecliptic",
+            Self::MeanEquator { .. } => "mean equator",
+            Self::TrueEquator { .. } => "true equator",
+            Self::Galactic => "galactic",
+            Self::Supergalactic => "supergalactic",
+        }
+    }
+}
+
+#[cfg(test)]
+mod frame_tests {
+    use crate::{ApproxEq, Tolerance};
+    use super::*;
+
+    #[test]
+    fn rotations_are_orthonormal() {
+        for f in [Frame::Galactic, Frame::Supergalactic, Frame::Ecliptic { epoch: 1950.0 }, Frame::TrueEquator { epoch: 2150.0 }] {
+            let m = mul_mat(&f.rotation_from_icrs(), &transpose(&f.rotation_from_icrs()));
+            for (i, row) in m.iter().enumerate() {
+                for (j, v) in row.iter().enumerate() {
+                    assert!(v.approx_eq(&if i == j { 1.0 } else { 0.0 }, Tolerance::absolute(1e-12)), "{}", f.name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn obliquity_j2000() {
+        assert!(obliquity(2000.0).deg().approx_eq(&23.439_279.deg(), Tolerance::absolute(1e-6)));
+        assert!(obliquity(2100.0) < obliquity(2000.0));
+    }
+}
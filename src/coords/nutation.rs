@@ -0,0 +1,71 @@
+//! IAU 2000B nutation (McCarthy & Luzum 2003), truncated to its 20 largest luni-solar terms.
+//!
+//! Good to ~10 mas over 1995-2050 or so - plenty for apparent places outside of VLBI work.
+use crate::{Angle, AsAngle, DefoAble, MetricsInternalType};
+
+/// Multipliers of l, l′, F, D, Ω, and the coefficients (0.1 µas, and per century) of
+/// sin/cos for Δψ and cos/sin for Δε.
+type Term = ([i8; 5], [MetricsInternalType; 6]);
+
+const TERMS: [Term; 20] = [
+    ([ 0, 0, 0, 0, 1], [-172_064_161.0, -174_666.0,  33_386.0, 92_052_331.0,  9_086.0,  15_377.0]),
+    ([ 0, 0, 2,-2, 2], [ -13_170_906.0,   -1_675.0, -13_696.0,  5_730_336.0, -3_015.0,  -4_587.0]),
+    ([ 0, 0, 2, 0, 2], [  -2_276_413.0,     -234.0,   2_796.0,    978_459.0,   -485.0,   1_374.0]),
+    ([ 0, 0, 0, 0, 2], [   2_074_554.0,      207.0,    -698.0,   -897_492.0,    470.0,    -291.0]),
+    ([ 0, 1, 0, 0, 0], [   1_475_877.0,   -3_633.0,  11_817.0,     73_871.0,   -184.0,  -1_924.0]),
+    ([ 0, 1, 2,-2, 2], [    -516_821.0,    1_226.0,    -524.0,    224_386.0,   -677.0,    -174.0]),
+    ([ 1, 0, 0, 0, 0], [     711_159.0,       73.0,    -872.0,     -6_750.0,      0.0,     358.0]),
+    ([ 0, 0, 2, 0, 1], [    -387_298.0,     -367.0,     380.0,    200_728.0,     18.0,     318.0]),
+    ([ 1, 0, 2, 0, 2], [    -301_461.0,      -36.0,     816.0,    129_025.0,    -63.0,     367.0]),
+    ([ 0,-1, 2,-2, 2], [     215_829.0,     -494.0,     111.0,    -95_929.0,    299.0,     132.0]),
+    ([ 0, 0, 2,-2, 1], [     128_227.0,      137.0,     181.0,    -68_982.0,     -9.0,      39.0]),
+    ([-1, 0, 2, 0, 2], [     123_457.0,       11.0,      19.0,    -53_311.0,     32.0,      -4.0]),
+    ([-1, 0, 0, 2, 0], [     156_994.0,       10.0,    -168.0,     -1_235.0,      0.0,      82.0]),
+    ([ 1, 0, 0, 0, 1], [      63_110.0,       63.0,      27.0,    -33_228.0,      0.0,      -9.0]),
+    ([-1, 0, 0, 0, 1], [     -57_976.0,      -63.0,    -189.0,     31_429.0,      0.0,     -75.0]),
+    ([-1, 0, 2, 2, 2], [     -59_641.0,      -11.0,     149.0,     25_543.0,    -11.0,      66.0]),
+    ([ 1, 0, 2, 0, 1], [     -51_613.0,      -42.0,     129.0,     26_366.0,      0.0,      78.0]),
+    ([-2, 0, 2, 0, 1], [      45_893.0,       50.0,      31.0,    -24_236.0,    -10.0,      20.0]),
+    ([ 0, 0, 0, 2, 0], [      63_384.0,       11.0,    -150.0,     -1_220.0,      0.0,      29.0]),
+    ([ 0, 0, 2, 2, 2], [     -38_571.0,       -1.0,     158.0,     16_452.0,    -11.0,      68.0]),
+];
+
+/// Fixed offsets standing in for the planetary terms, in arcsec.
+const DPSI_PLANETARY: MetricsInternalType = -0.000_135;
+const DEPS_PLANETARY: MetricsInternalType = 0.000_388;
+
+/// Luni-solar fundamental arguments l, l′, F, D, Ω (Simon et al. 1994, linear terms), in radians.
+fn fundamental_arguments(t: MetricsInternalType) -> [MetricsInternalType; 5] {
+    [
+        485_868.249_036 + 1_717_915_923.217_8 * t,
+        1_287_104.793_05 + 129_596_581.048_1 * t,
+        335_779.526_232 + 1_739_527_262.847_8 * t,
+        1_072_260.703_69 + 1_602_961_601.209_0 * t,
+        450_160.398_036 - 6_962_890.543_1 * t,
+    ].map(|a| Angle::ArcSec(a.rem_euclid(1_296_000.0)).rad().raw())
+}
+
+/// Nutation in longitude and obliquity, (Δψ, Δε), at `t` Julian centuries (TT) since J2000.0.
+pub fn nutation(t: MetricsInternalType) -> (Angle, Angle) {
+    let fa = fundamental_arguments(t);
+    let (dpsi, deps) = TERMS.iter().rev().fold((0.0, 0.0), |(dp, de), (n, c)| {
+        let arg: MetricsInternalType = n.iter().zip(fa).map(|(n, a)| *n as MetricsInternalType * a).sum();
+        let (s, co) = arg.sin_cos();
+        (dp + (c[0] + c[1] * t) * s + c[2] * co, de + (c[3] + c[4] * t) * co + c[5] * s)
+    });
+    (Angle::ArcSec(dpsi * 1e-7 + DPSI_PLANETARY), Angle::ArcSec(deps * 1e-7 + DEPS_PLANETARY))
+}
+
+#[cfg(test)]
+mod nutation_tests {
+    use crate::{ApproxEq, AsAngle, Tolerance};
+    use super::*;
+
+    #[test]
+    fn against_full_2000b() {
+        // SOFA iauNut00b at MJD 53736 TT: Δψ = -0.963 255 229e-5 rad, Δε = 0.406 319 711e-4 rad.
+        let (dpsi, deps) = nutation((53_736.0 - 51_544.5) / 36_525.0);
+        assert!(dpsi.approx_eq(&(-0.963_255_229_114_836_3e-5).rad().arcsec(), Tolerance::absolute(0.01)), "{dpsi:.4}");
+        assert!(deps.approx_eq(&0.406_319_710_662_115_9e-4.rad().arcsec(), Tolerance::absolute(0.01)), "{deps:.4}");
+    }
+}
@@ -0,0 +1,50 @@
+//! IAU 2006 precession (Fukushima-Williams angles, incl. frame bias) - Hilton et al. (2006), Capitaine et al. (2003).
+use crate::{Angle, MetricsInternalType};
+
+use super::{frame::{Mat3, mul_mat, obliquity, rot_x, rot_z}, nutation::nutation};
+
+/// Evaluate a polynomial in `t`, coefficients lowest power first.
+fn poly(t: MetricsInternalType, c: &[MetricsInternalType]) -> MetricsInternalType {
+    c.iter().rev().fold(0.0, |acc, c| acc * t + c)
+}
+
+/// Fukushima-Williams angles γ̄, φ̄, ψ̄, ε_A at `t` Julian centuries (TT) since J2000.0.
+fn fw_angles(t: MetricsInternalType) -> (Angle, Angle, Angle, Angle) {
+    (
+        Angle::ArcSec(poly(t, &[-0.052_928, 10.556_378, 0.493_204_4, -0.000_312_38, -0.000_002_788, 0.000_000_026_0])),
+        Angle::ArcSec(poly(t, &[84_381.412_819, -46.811_016, 0.051_126_8, 0.000_532_89, -0.000_000_440, -0.000_000_017_6])),
+        Angle::ArcSec(poly(t, &[-0.041_775, 5_038.481_484, 1.558_417_5, -0.000_185_22, -0.000_026_452, -0.000_000_014_8])),
+        obliquity(2000.0 + 100.0 * t),
+    )
+}
+
+/// `Rx(-ε) Rz(-ψ) Rx(φ̄) Rz(γ̄)`
+fn fw_matrix(gamma: Angle, phi: Angle, psi: Angle, eps: Angle) -> Mat3 {
+    mul_mat(&rot_x(-eps), &mul_mat(&rot_z(-psi), &mul_mat(&rot_x(phi), &rot_z(gamma))))
+}
+
+/// GCRS → mean equator and equinox of date.
+pub(crate) fn bias_precession(t: MetricsInternalType) -> Mat3 {
+    let (gamma, phi, psi, eps) = fw_angles(t);
+    fw_matrix(gamma, phi, psi, eps)
+}
+
+/// GCRS → true equator and equinox of date.
+pub(crate) fn bias_precession_nutation(t: MetricsInternalType) -> Mat3 {
+    let (gamma, phi, psi, eps) = fw_angles(t);
+    let (dpsi, deps) = nutation(t);
+    fw_matrix(gamma, phi, psi + dpsi, eps + deps)
+}
+
+#[cfg(test)]
+mod precession_tests {
+    use crate::{AsAngle, DefoAble, Frame, SkyCoord};
+
+    #[test]
+    fn half_a_century() {
+        // m = 46.1″/yr and n = 20.0″/yr at α = δ = 0.
+        let c = SkyCoord::icrs(0.deg(), 0.deg()).unwrap().to_frame(Frame::MeanEquator { epoch: 2050.0 });
+        assert!((c.lon().arcsec().raw() - 2_305.0).abs() < 2.0, "{}", c.lon().arcsec());
+        assert!((c.lat().arcsec().raw() - 1_002.0).abs() < 2.0, "{}", c.lat().arcsec());
+    }
+}
@@ -0,0 +1,166 @@
+//! Derived quantities - the results of multiplying/dividing the base metrics with each other.
+//! 
+//! Area, Volume, Density, Velocity, Acceleration, and Luminosity.
+use crate::{Angle, AsMass, AsSpatialUnit, AsTime, AstroError, Checked, ConstantSet, DefoAble, Mass, MetricsInternalType, SpatialUnit, Temperature, AsTemperature, Time, dimensional};
+
+mod area;
+mod volume;
+mod density;
+mod velocity;
+mod acceleration;
+mod luminosity;
+pub use area::{Area, AsArea};
+pub use volume::{Volume, AsVolume};
+pub use density::{Density, AsDensity};
+pub use velocity::{Velocity, AsVelocity};
+pub use acceleration::{Acceleration, AsAcceleration, STANDARD_GRAVITY};
+pub use luminosity::{Luminosity, AsLuminosity};
+
+/// GM of `mass`, m³/s² - via GM☉, which is known far better than G itself.
+pub(crate) fn gm(mass: Mass) -> Result<MetricsInternalType, AstroError> {
+    Ok(mass.validate()?.mo().raw() * ConstantSet::DEFAULT.gm_sun.value)
+}
+
+/// `radius` in meters, if it's something to divide by.
+pub(crate) fn radius_m(radius: SpatialUnit) -> Result<MetricsInternalType, AstroError> {
+    match radius.validate()?.m().raw() {
+        0.0 => Err(AstroError::DivisionByZero),
+        r => Ok(r)
+    }
+}
+
+// Same-kind quotients are dimensionless ratios.
+dimensional!(Mass / Mass => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(SpatialUnit / SpatialUnit => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+// Ratios of temperatures only make sense on an absolute scale…
+dimensional!(Temperature / Temperature => MetricsInternalType, |a, b| a.k().raw() / b.k().raw());
+dimensional!(Area / Area => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Volume / Volume => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Density / Density => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Time / Time => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Velocity / Velocity => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Acceleration / Acceleration => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Luminosity / Luminosity => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+dimensional!(Angle / Angle => MetricsInternalType, |a, b| a.raw() / b.cnv_into(a).raw());
+
+// L × L = L², L² × L = L³, and back again.
+dimensional!(SpatialUnit * SpatialUnit => Area, |a, b| Area::M2(a.m().raw() * b.m().raw()));
+dimensional!(Area * SpatialUnit => Volume, |a, b| Volume::M3(a.m2().raw() * b.m().raw()));
+dimensional!(SpatialUnit * Area => Volume, |a, b| Volume::M3(a.m().raw() * b.m2().raw()));
+dimensional!(Area / SpatialUnit => SpatialUnit, |a, b| SpatialUnit::M(a.m2().raw() / b.m().raw()));
+dimensional!(Volume / SpatialUnit => Area, |a, b| Area::M2(a.m3().raw() / b.m().raw()));
+dimensional!(Volume / Area => SpatialUnit, |a, b| SpatialUnit::M(a.m3().raw() / b.m2().raw()));
+
+// M / L³ = ρ, and ρ × L³ = M.
+dimensional!(Mass / Volume => Density, |a, b| Density::KgM3(a.kg().raw() / b.m3().raw()));
+dimensional!(Mass / Density => Volume, |a, b| Volume::M3(a.kg().raw() / b.kg_m3().raw()));
+dimensional!(Density * Volume => Mass, |a, b| Mass::Kg(a.kg_m3().raw() * b.m3().raw()));
+dimensional!(Volume * Density => Mass, |a, b| Mass::Kg(a.m3().raw() * b.kg_m3().raw()));
+
+// L / T = v, v × T = L, and L / v = T.
+dimensional!(SpatialUnit / Time => Velocity, |a, b| Velocity::MS(a.m().raw() / b.s().raw()));
+dimensional!(Velocity * Time => SpatialUnit, |a, b| SpatialUnit::M(a.m_s().raw() * b.s().raw()));
+dimensional!(Time * Velocity => SpatialUnit, |a, b| SpatialUnit::M(a.s().raw() * b.m_s().raw()));
+dimensional!(SpatialUnit / Velocity => Time, |a, b| Time::S(a.m().raw() / b.m_s().raw()));
+
+// v / T = a, a × T = v, and v / a = T.
+dimensional!(Velocity / Time => Acceleration, |a, b| Acceleration::MS2(a.m_s().raw() / b.s().raw()));
+dimensional!(Acceleration * Time => Velocity, |a, b| Velocity::MS(a.m_s2().raw() * b.s().raw()));
+dimensional!(Time * Acceleration => Velocity, |a, b| Velocity::MS(a.s().raw() * b.m_s2().raw()));
+dimensional!(Velocity / Acceleration => Time, |a, b| Time::S(a.m_s().raw() / b.m_s2().raw()));
+
+// Small angles: s = θd, and d = s/θ.
+dimensional!(SpatialUnit * Angle => SpatialUnit, |a, b| b.size_at(*a));
+dimensional!(Angle * SpatialUnit => SpatialUnit, |a, b| a.size_at(*b));
+dimensional!(SpatialUnit / Angle => SpatialUnit, |a, b| b.distance_for(*a));
+
+#[cfg(test)]
+mod derived_tests {
+    use crate::{ApproxEq, AsAcceleration, AsAngle, AsCelestialRadii, AsDensity, AsVelocity, AsMass, AsSpatialUnit, AsTemperature, AstroError, PI, Tolerance};
+    use super::*;
+
+    #[test]
+    fn products_and_quotients() {
+        let area = 2.m() * 3.m();
+        assert_eq!(6.m2(), area);
+        let volume = area * 1.m();
+        assert_eq!(6.m3(), volume);
+        assert_eq!(3.m(), volume / 2.m2());
+        assert_eq!(1000.m(), 1.km2() / 1000.m());
+    }
+
+    #[test]
+    fn ratios() {
+        assert_eq!(2.0, 2.kg() / 1000.g());
+        assert_eq!(0.5, 1.ly() / 2.ly());
+        assert_eq!(2.0, 200.k() / 100.k());
+        assert!((Temperature::X / 100.k()).is_nan());
+    }
+
+    #[test]
+    fn density_of_earth() {
+        let r = 1.re();
+        let v = r * r * r * (4.0 / 3.0 * PI);
+        let rho = 1.me() / v;
+        assert!((rho.g_cm3().raw() - 5.5).abs() < 0.1);
+        assert!((1.me() / (rho * v) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn area_volume_density_formatting() {
+        assert_eq!("1.0 km²", 1.km2().to_string());
+        assert_eq!("1.000 au²", 1.au2().to_string());
+        assert_eq!("2.50 m2", format!("{:#.2}", 2.5.m2()));
+        assert_eq!("1.1e21 m³", format!("{:.1e}", 1.083e21.m3()));
+        assert_eq!("   5.514 g/cm³", format!("{:>14}", 5.514.g_cm3()));
+        assert_eq!("5514.0 kg/m3", format!("{:#}", 5514.kg_m3()));
+    }
+
+    #[test]
+    fn velocities() {
+        assert_eq!(1.yr(), (1.ly() / 1.of_c()).yr());
+        let v = 1.au() / 1.yr();
+        assert!(v.km_s().approx_eq(&4.740_470_463_5.km_s(), Tolerance::relative(1e-9)));
+        assert!(1.pc_myr().km_s().approx_eq(&0.977_792_2.km_s(), Tolerance::relative(1e-6)));
+        assert_eq!(1.au(), v * 1.yr());
+        assert_eq!(2.ly(), 2.yr() * 1.of_c());
+        assert_eq!(Ok(29.78.km_s()), "29.78 km/s".parse());
+        assert_eq!("0.1000 c", 0.1.of_c().to_string());
+        assert_eq!(2.0, 2.deg() / 1.deg());
+        assert!((1.pc() * 1.arcsec()).au().approx_eq(&1.au(), Tolerance::relative(1e-12)));
+        assert!((1.au() / 1.arcsec()).pc().approx_eq(&1.pc(), Tolerance::relative(1e-12)));
+    }
+
+    #[test]
+    fn luminosity_of_sun() {
+        // IAU 2015 B3 nominal T_eff☉ = 5772 K.
+        let l = Luminosity::stefan_boltzmann(1.ro(), 5772.k()).unwrap();
+        assert!(l.approx_eq(&1.lo(), Tolerance::relative(1e-3)));
+        assert!(l.effective_temperature(1.ro()).unwrap().approx_eq(&5772.k(), Tolerance::relative(1e-12)));
+        assert_eq!(3.828e33.erg_s(), 1.lo());
+        assert!(matches!(Luminosity::stefan_boltzmann((-1).ro(), 5772.k()), Err(AstroError::NegativeLength(_))));
+        assert!(Luminosity::stefan_boltzmann(1.re(), Temperature::X).is_err());
+        assert_eq!("1.000 L☉", 1.lo().to_string());
+    }
+
+    #[test]
+    fn earth_like_body() {
+        let g = Acceleration::surface_gravity(1.me(), 1.re()).unwrap();
+        // Equatorial radius, no rotation - a hair under g₀.
+        assert!(g.approx_eq(&9.798.m_s2(), Tolerance::relative(1e-3)), "{g}");
+        assert!(g.g0().approx_eq(&1.g0(), Tolerance::relative(2e-3)));
+        assert!(Velocity::escape(1.me(), 1.re()).unwrap().approx_eq(&11.18.km_s(), Tolerance::relative(1e-3)));
+        // The ISS, ~400 km up.
+        assert!(Velocity::circular_orbit(1.me(), 1.re(), 400_000.m()).unwrap().approx_eq(&7.67.km_s(), Tolerance::relative(2e-3)));
+        assert!(Density::mean(1.me(), 1.re()).unwrap().approx_eq(&5.51.g_cm3(), Tolerance::relative(5e-3)));
+        assert!((Acceleration::surface_gravity(1.mo(), 1.ro()).unwrap().log_g() - 4.438).abs() < 1e-3);
+        assert!(matches!(Velocity::escape(1.me(), 0.re()), Err(AstroError::DivisionByZero)));
+        assert!(matches!(Density::mean((-1).me(), 1.re()), Err(AstroError::NegativeMass(_))));
+        // a × T = v, and the lot.
+        assert_eq!(9.806_65.m_s(), 1.g0() * 1.s());
+        assert_eq!(1.g0(), 9.806_65.m_s() / 1.s());
+        assert_eq!(Ok(274.cm_s2()), "274 Gal".parse());
+        assert_eq!("1.00 g₀", 1.g0().to_string());
+        assert_eq!("1.00 m/s2", format!("{:#}", 1.m_s2()));
+    }
+}
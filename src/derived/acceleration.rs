@@ -0,0 +1,135 @@
+//! Acceleration
+//! 
+//! cm/s² (for log g), m/s², and standard gravity g₀.
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}, str::FromStr};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{AstroError, DefoAble, Mass, MetricsInternalType, SpatialUnit, UnitTable, derived::{gm, radius_m}, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, parse::{ParseMetricError, UnitRow, parse_metric}, units::UnitFactor};
+
+/// Standard gravity g₀, m/s² - exact, by definition (CGPM 1901).
+pub const STANDARD_GRAVITY: MetricsInternalType = 9.806_65;
+
+/// Some acceleration "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Acceleration {
+    /// Centimeters per second squared - the unit of stellar log g.
+    CmS2(MetricsInternalType),
+    /// Meters per second squared.
+    MS2(MetricsInternalType),
+    /// Multiples of standard gravity, g₀.
+    G0(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Acceleration]-type.
+pub trait AsAcceleration {
+    /// self → cm/s²
+    fn cm_s2(&self) -> Acceleration;
+    /// self → m/s²
+    fn m_s2(&self) -> Acceleration;
+    /// self → g₀
+    fn g0(&self) -> Acceleration;
+}
+
+impl UnitTable for Acceleration {
+    const SI_UNIT: &'static str = "m/s²";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::CmS2(0.0), |_| 1e-2),
+        (Self::MS2(0.0), |_| 1.0),
+        (Self::G0(0.0), |_| STANDARD_GRAVITY),
+    ];
+}
+
+impl AsAcceleration for Acceleration {
+    fn cm_s2(&self) -> Acceleration { self.to_unit(&Self::CmS2(0.0)) }
+    fn m_s2(&self) -> Acceleration { self.to_unit(&Self::MS2(0.0)) }
+    fn g0(&self) -> Acceleration { self.to_unit(&Self::G0(0.0)) }
+}
+
+impl Acceleration {
+    /// Surface gravity `GM/R²` of a body of `mass` and `radius`, as [Acceleration::MS2].
+    pub fn surface_gravity(mass: Mass, radius: SpatialUnit) -> Result<Acceleration, AstroError> {
+        Ok(Self::MS2(gm(mass)? / radius_m(radius)?.powi(2)))
+    }
+
+    /// log₁₀ of `self` in cm/s², as used for stellar surface gravities - the Sun is ~4.44.
+    pub fn log_g(&self) -> MetricsInternalType {
+        self.cm_s2().raw().log10()
+    }
+
+    /// From a (cgs) log g.
+    pub fn from_log_g(log_g: MetricsInternalType) -> Self {
+        Self::CmS2(MetricsInternalType::powf(10.0, log_g))
+    }
+}
+
+impl DefoAble for Acceleration {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::CmS2(v) |
+            Self::MS2(v)  |
+            Self::G0(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::CmS2(v) |
+            Self::MS2(v)  |
+            Self::G0(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Acceleration {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Acceleration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Acceleration {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::CmS2(_), Symbols::Unicode) => "cm/s²",
+            (Self::CmS2(_), Symbols::Ascii) => "cm/s2",
+            (Self::MS2(_), Symbols::Unicode) => "m/s²",
+            (Self::MS2(_), Symbols::Ascii) => "m/s2",
+            (Self::G0(_), Symbols::Unicode) => "g₀",
+            (Self::G0(_), Symbols::Ascii) => "g0",
+        }
+    }
+
+    fn default_decimals(&self) -> usize { 2 }
+}
+
+display_via_format!(Acceleration);
+
+/// Accepted unit symbols.
+const ACCELERATION_UNITS: &[UnitRow<Acceleration>] = &[
+    ("cm/s²", Acceleration::CmS2), ("cm/s2", Acceleration::CmS2), ("Gal", Acceleration::CmS2),
+    ("m/s²", Acceleration::MS2), ("m/s2", Acceleration::MS2),
+    ("g₀", Acceleration::G0), ("g0", Acceleration::G0), ("g⊕", Acceleration::G0),
+];
+
+impl FromStr for Acceleration {
+    type Err = ParseMetricError;
+    /// Parse e.g. "9.81 m/s²", "2.5 g0".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, ACCELERATION_UNITS)
+    }
+}
+
+define_as_for_prim!(AsAcceleration => Acceleration [cm_s2 => CmS2, m_s2 => MS2, g0 => G0]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Acceleration; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
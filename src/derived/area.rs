@@ -0,0 +1,105 @@
+//! Area
+//! 
+//! m², km², and au².
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{DefoAble, MetricsInternalType, UnitTable, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, units::UnitFactor};
+
+/// Some area "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Area {
+    /// Square meters.
+    M2(MetricsInternalType),
+    /// Square kilometers.
+    Km2(MetricsInternalType),
+    /// Square astronomical units.
+    Au2(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Area]-type.
+pub trait AsArea {
+    /// self → m²
+    fn m2(&self) -> Area;
+    /// self → km²
+    fn km2(&self) -> Area;
+    /// self → au²
+    fn au2(&self) -> Area;
+}
+
+impl UnitTable for Area {
+    const SI_UNIT: &'static str = "m²";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::M2(0.0), |_| 1.0),
+        (Self::Km2(0.0), |_| 1e6),
+        (Self::Au2(0.0), |cs| cs.au.value * cs.au.value),
+    ];
+}
+
+impl AsArea for Area {
+    fn m2(&self) -> Area { self.to_unit(&Self::M2(0.0)) }
+    fn km2(&self) -> Area { self.to_unit(&Self::Km2(0.0)) }
+    fn au2(&self) -> Area { self.to_unit(&Self::Au2(0.0)) }
+}
+
+impl DefoAble for Area {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::M2(v)  |
+            Self::Km2(v) |
+            Self::Au2(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::M2(v)  |
+            Self::Km2(v) |
+            Self::Au2(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Area {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Area {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Area {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::M2(_), Symbols::Unicode) => "m²",
+            (Self::M2(_), Symbols::Ascii) => "m2",
+            (Self::Km2(_), Symbols::Unicode) => "km²",
+            (Self::Km2(_), Symbols::Ascii) => "km2",
+            (Self::Au2(_), Symbols::Unicode) => "au²",
+            (Self::Au2(_), Symbols::Ascii) => "au2",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::Au2(_) => 3,
+            _ => 1
+        }
+    }
+}
+
+display_via_format!(Area);
+
+define_as_for_prim!(AsArea => Area [m2 => M2, km2 => Km2, au2 => Au2]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Area; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
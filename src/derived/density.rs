@@ -0,0 +1,103 @@
+//! Density
+//! 
+//! kg/m³ and g/cm³.
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{AsMass, AstroError, Checked, DefoAble, Mass, MetricsInternalType, PI, SpatialUnit, UnitTable, defo, derived::radius_m, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, units::UnitFactor};
+
+/// Some (mass) density "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Density {
+    /// Kilograms per cubic meter.
+    KgM3(MetricsInternalType),
+    /// Grams per cubic centimeter.
+    GCm3(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Density]-type.
+pub trait AsDensity {
+    /// self → kg/m³
+    fn kg_m3(&self) -> Density;
+    /// self → g/cm³
+    fn g_cm3(&self) -> Density;
+}
+
+impl UnitTable for Density {
+    const SI_UNIT: &'static str = "kg/m³";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::KgM3(0.0), |_| 1.0),
+        (Self::GCm3(0.0), |_| 1e3),
+    ];
+}
+
+impl AsDensity for Density {
+    fn kg_m3(&self) -> Density { self.to_unit(&Self::KgM3(0.0)) }
+    fn g_cm3(&self) -> Density { self.to_unit(&Self::GCm3(0.0)) }
+}
+
+impl Density {
+    /// Mean density of a sphere of `mass` and `radius`, as [Density::GCm3].
+    pub fn mean(mass: Mass, radius: SpatialUnit) -> Result<Density, AstroError> {
+        let r = radius_m(radius)?;
+        Ok(Self::KgM3(mass.validate()?.kg().raw() / (4.0 / 3.0 * PI * r.powi(3))).g_cm3())
+    }
+}
+
+impl DefoAble for Density {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::KgM3(v) |
+            Self::GCm3(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::KgM3(v) |
+            Self::GCm3(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Density {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Density {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Density {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::KgM3(_), Symbols::Unicode) => "kg/m³",
+            (Self::KgM3(_), Symbols::Ascii) => "kg/m3",
+            (Self::GCm3(_), Symbols::Unicode) => "g/cm³",
+            (Self::GCm3(_), Symbols::Ascii) => "g/cm3",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::GCm3(_) => 3,
+            _ => 1
+        }
+    }
+}
+
+display_via_format!(Density);
+
+define_as_for_prim!(AsDensity => Density [kg_m3 => KgM3, g_cm3 => GCm3]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Density; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
@@ -0,0 +1,145 @@
+//! Luminosity
+//! 
+//! W, erg/s, and L☉.
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}, str::FromStr};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{AsSpatialUnit, AstroError, Checked, ConstantSet, DefoAble, MetricsInternalType, PI, SpatialUnit, Temperature, UnitTable, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, parse::{ParseMetricError, UnitRow, parse_metric}, units::UnitFactor};
+
+/// Some luminosity (radiant power) "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Luminosity {
+    /// Ergs per second, 10⁻⁷ W.
+    ErgS(MetricsInternalType),
+    /// Watts.
+    W(MetricsInternalType),
+    /// Solar luminosities (nominal, IAU 2015 B3).
+    LO(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Luminosity]-type.
+pub trait AsLuminosity {
+    /// self → erg/s
+    fn erg_s(&self) -> Luminosity;
+    /// self → W
+    fn w(&self) -> Luminosity;
+    /// self → L☉
+    fn lo(&self) -> Luminosity;
+}
+
+impl UnitTable for Luminosity {
+    const SI_UNIT: &'static str = "W";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::ErgS(0.0), |_| 1e-7),
+        (Self::W(0.0), |_| 1.0),
+        (Self::LO(0.0), |cs| cs.l_sun.value),
+    ];
+}
+
+impl AsLuminosity for Luminosity {
+    fn erg_s(&self) -> Luminosity { self.to_unit(&Self::ErgS(0.0)) }
+    fn w(&self) -> Luminosity { self.to_unit(&Self::W(0.0)) }
+    fn lo(&self) -> Luminosity { self.to_unit(&Self::LO(0.0)) }
+}
+
+impl Luminosity {
+    /// Black body luminosity, `L = 4πR²σT⁴`, of a sphere with the given `radius` and (effective) `temperature`.
+    /// 
+    /// Fails if either is invalid, e.g. a negative radius or [Temperature::X].
+    pub fn stefan_boltzmann(radius: SpatialUnit, temperature: Temperature) -> Result<Luminosity, AstroError> {
+        Self::stefan_boltzmann_with(radius, temperature, &ConstantSet::DEFAULT)
+    }
+
+    /// [Luminosity::stefan_boltzmann] against the given [ConstantSet].
+    pub fn stefan_boltzmann_with(radius: SpatialUnit, temperature: Temperature, cs: &ConstantSet) -> Result<Luminosity, AstroError> {
+        let r = radius.validate()?.to_unit_with(&SpatialUnit::M(0.0), cs).raw();
+        let t = temperature.checked_k()?.raw();
+        Ok(Self::W(4.0 * PI * r * r * cs.sigma.value * t.powi(4)).to_unit_with(&Self::LO(0.0), cs))
+    }
+
+    /// Effective temperature of a black body of the given `radius` radiating `self`.
+    pub fn effective_temperature(&self, radius: SpatialUnit) -> Result<Temperature, AstroError> {
+        let cs = &ConstantSet::DEFAULT;
+        let l = self.validate()?.w().raw();
+        let r = radius.validate()?.m().raw();
+        if r == 0.0 {
+            return Err(AstroError::DivisionByZero);
+        }
+        Ok(Temperature::K((l / (4.0 * PI * r * r * cs.sigma.value)).powf(0.25)))
+    }
+}
+
+impl DefoAble for Luminosity {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::ErgS(v) |
+            Self::W(v)    |
+            Self::LO(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::ErgS(v) |
+            Self::W(v)    |
+            Self::LO(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Luminosity {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Luminosity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Luminosity {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::ErgS(_), _) => "erg/s",
+            (Self::W(_), _) => "W",
+            (Self::LO(_), Symbols::Unicode) => "L☉",
+            (Self::LO(_), Symbols::Ascii) => "Lsun",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::LO(_) => 3,
+            _ => 1
+        }
+    }
+}
+
+display_via_format!(Luminosity);
+
+/// Accepted unit symbols.
+const LUMINOSITY_UNITS: &[UnitRow<Luminosity>] = &[
+    ("erg/s", Luminosity::ErgS),
+    ("W", Luminosity::W),
+    ("L☉", Luminosity::LO), ("Lsun", Luminosity::LO), ("Lo", Luminosity::LO),
+];
+
+impl FromStr for Luminosity {
+    type Err = ParseMetricError;
+    /// Parse e.g. "3.828e26 W", "25.4 L☉".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, LUMINOSITY_UNITS)
+    }
+}
+
+define_as_for_prim!(AsLuminosity => Luminosity [erg_s => ErgS, w => W, lo => LO]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Luminosity; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
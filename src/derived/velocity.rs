@@ -0,0 +1,153 @@
+//! Velocity
+//! 
+//! m/s, km/s, AU/yr, pc/Myr, and fractions of c.
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}, str::FromStr};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{AsSpatialUnit, AstroError, Checked, DefoAble, JULIAN_YEAR_SECONDS, Mass, MetricsInternalType, SpatialUnit, UnitTable, derived::{gm, radius_m}, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, parse::{ParseMetricError, UnitRow, parse_metric}, units::UnitFactor};
+
+/// Some velocity "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Velocity {
+    /// Meters per second.
+    MS(MetricsInternalType),
+    /// Parsecs per megayear (Julian), ≈0.978 km/s.
+    PcMyr(MetricsInternalType),
+    /// Kilometers per second.
+    KmS(MetricsInternalType),
+    /// Astronomical units per Julian year, ≈4.74 km/s.
+    AuYr(MetricsInternalType),
+    /// Fraction of the speed of light.
+    C(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Velocity]-type.
+pub trait AsVelocity {
+    /// self → m/s
+    fn m_s(&self) -> Velocity;
+    /// self → km/s
+    fn km_s(&self) -> Velocity;
+    /// self → AU/yr
+    fn au_yr(&self) -> Velocity;
+    /// self → pc/Myr
+    fn pc_myr(&self) -> Velocity;
+    /// self → fraction of c
+    ///
+    /// Not plain `c()`, as that's Celsius in [AsTemperature][crate::AsTemperature].
+    fn of_c(&self) -> Velocity;
+}
+
+impl UnitTable for Velocity {
+    const SI_UNIT: &'static str = "m/s";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::MS(0.0), |_| 1.0),
+        (Self::PcMyr(0.0), |cs| cs.pc.value / (1e6 * JULIAN_YEAR_SECONDS)),
+        (Self::KmS(0.0), |_| 1e3),
+        (Self::AuYr(0.0), |cs| cs.au.value / JULIAN_YEAR_SECONDS),
+        (Self::C(0.0), |cs| cs.c.value),
+    ];
+}
+
+impl AsVelocity for Velocity {
+    fn m_s(&self) -> Velocity { self.to_unit(&Self::MS(0.0)) }
+    fn km_s(&self) -> Velocity { self.to_unit(&Self::KmS(0.0)) }
+    fn au_yr(&self) -> Velocity { self.to_unit(&Self::AuYr(0.0)) }
+    fn pc_myr(&self) -> Velocity { self.to_unit(&Self::PcMyr(0.0)) }
+    fn of_c(&self) -> Velocity { self.to_unit(&Self::C(0.0)) }
+}
+
+impl Velocity {
+    /// Escape velocity `√(2GM/R)` from the surface of a body of `mass` and `radius`, as [Velocity::KmS].
+    pub fn escape(mass: Mass, radius: SpatialUnit) -> Result<Velocity, AstroError> {
+        Ok(Self::MS((2.0 * gm(mass)? / radius_m(radius)?).sqrt()).km_s())
+    }
+
+    /// Circular orbital velocity `√(GM/(R + h))` at `altitude` above a body of `mass` and `radius`, as [Velocity::KmS].
+    pub fn circular_orbit(mass: Mass, radius: SpatialUnit, altitude: SpatialUnit) -> Result<Velocity, AstroError> {
+        let r = radius.validate()?.m().raw() + altitude.validate()?.m().raw();
+        Ok(Self::MS((gm(mass)? / radius_m(SpatialUnit::M(r))?).sqrt()).km_s())
+    }
+}
+
+impl DefoAble for Velocity {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::MS(v)    |
+            Self::PcMyr(v) |
+            Self::KmS(v)   |
+            Self::AuYr(v)  |
+            Self::C(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::MS(v)    |
+            Self::PcMyr(v) |
+            Self::KmS(v)   |
+            Self::AuYr(v)  |
+            Self::C(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Velocity {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Velocity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Velocity {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, _: Symbols) -> &'static str {
+        match self {
+            Self::MS(_) => "m/s",
+            Self::KmS(_) => "km/s",
+            Self::AuYr(_) => "AU/yr",
+            Self::PcMyr(_) => "pc/Myr",
+            Self::C(_) => "c",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::MS(_) => 1,
+            Self::C(_) => 4,
+            _ => 2
+        }
+    }
+}
+
+display_via_format!(Velocity);
+
+/// Accepted unit symbols.
+const VELOCITY_UNITS: &[UnitRow<Velocity>] = &[
+    ("m/s", Velocity::MS),
+    ("km/s", Velocity::KmS),
+    ("AU/yr", Velocity::AuYr), ("au/a", Velocity::AuYr),
+    ("pc/Myr", Velocity::PcMyr),
+    ("c", Velocity::C),
+];
+
+impl FromStr for Velocity {
+    type Err = ParseMetricError;
+    /// Parse e.g. "29.78 km/s", "0.1 c".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, VELOCITY_UNITS)
+    }
+}
+
+define_as_for_prim!(AsVelocity => Velocity [m_s => MS, km_s => KmS, au_yr => AuYr, pc_myr => PcMyr, of_c => C]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Velocity; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
@@ -0,0 +1,100 @@
+//! Volume
+//! 
+//! cm³, m³, and km³.
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}};
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{DefoAble, MetricsInternalType, UnitTable, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, units::UnitFactor};
+
+/// Some volume "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Volume {
+    /// Cubic centimeters.
+    Cm3(MetricsInternalType),
+    /// Cubic meters.
+    M3(MetricsInternalType),
+    /// Cubic kilometers.
+    Km3(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Volume]-type.
+pub trait AsVolume {
+    /// self → cm³
+    fn cm3(&self) -> Volume;
+    /// self → m³
+    fn m3(&self) -> Volume;
+    /// self → km³
+    fn km3(&self) -> Volume;
+}
+
+impl UnitTable for Volume {
+    const SI_UNIT: &'static str = "m³";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::Cm3(0.0), |_| 1e-6),
+        (Self::M3(0.0), |_| 1.0),
+        (Self::Km3(0.0), |_| 1e9),
+    ];
+}
+
+impl AsVolume for Volume {
+    fn cm3(&self) -> Volume { self.to_unit(&Self::Cm3(0.0)) }
+    fn m3(&self) -> Volume { self.to_unit(&Self::M3(0.0)) }
+    fn km3(&self) -> Volume { self.to_unit(&Self::Km3(0.0)) }
+}
+
+impl DefoAble for Volume {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::Cm3(v) |
+            Self::M3(v)  |
+            Self::Km3(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::Cm3(v) |
+            Self::M3(v)  |
+            Self::Km3(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Volume {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Volume {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Volume {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::Cm3(_), Symbols::Unicode) => "cm³",
+            (Self::Cm3(_), Symbols::Ascii) => "cm3",
+            (Self::M3(_), Symbols::Unicode) => "m³",
+            (Self::M3(_), Symbols::Ascii) => "m3",
+            (Self::Km3(_), Symbols::Unicode) => "km³",
+            (Self::Km3(_), Symbols::Ascii) => "km3",
+        }
+    }
+
+    fn default_decimals(&self) -> usize { 1 }
+}
+
+display_via_format!(Volume);
+
+define_as_for_prim!(AsVolume => Volume [cm3 => Cm3, m3 => M3, km3 => Km3]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Volume; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
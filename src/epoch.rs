@@ -0,0 +1,367 @@
+//! Epochs and time scales
+//! 
+//! A moment in time as a two-part Julian Date in one of the [TimeScale]s.
+use std::{cmp::Ordering, fmt::Display, ops::{Add, Sub}};
+
+use serde::{Deserialize, Serialize};
+
+mod leap_seconds;
+use crate::{AsTime, AstroError, DAY_SECONDS, DefoAble, JULIAN_YEAR_DAYS, MetricsInternalType, Time};
+
+/// JD of J2000.0 (2000-01-01 12:00 TT).
+pub const J2000_JD: MetricsInternalType = 2_451_545.0;
+/// JD - MJD.
+pub const MJD_OFFSET: MetricsInternalType = 2_400_000.5;
+/// Length of the Besselian (tropical, B1900) year in days, as used for Besselian epochs.
+const BESSELIAN_YEAR_DAYS: MetricsInternalType = 365.242_198_781;
+/// TT - TAI, in seconds.
+const TT_MINUS_TAI: MetricsInternalType = 32.184;
+
+/// Astronomical time scales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum TimeScale {
+    /// Coordinated Universal Time - civil time, with leap seconds.
+    Utc,
+    /// International Atomic Time.
+    Tai,
+    /// Terrestrial Time, TAI + 32.184 s.
+    Tt,
+    /// Barycentric Dynamical Time.
+    Tdb,
+}
+
+impl TimeScale {
+    /// Abbreviation, e.g. "TT".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utc => "UTC",
+            Self::Tai => "TAI",
+            Self::Tt => "TT",
+            Self::Tdb => "TDB",
+        }
+    }
+}
+
+/// A moment in time.
+/// 
+/// Stored as a two-part Julian Date (whole days + fraction) to keep sub-millisecond precision.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct Epoch {
+    jd1: MetricsInternalType,
+    jd2: MetricsInternalType,
+    scale: TimeScale,
+}
+
+/// MJD of 0h on the given (proleptic Gregorian) date.
+pub(crate) fn calendar_to_mjd(year: i32, month: u32, day: u32) -> MetricsInternalType {
+    // Fliegel & Van Flandern (1968); JDN at noon.
+    let (y, m, d) = (year as i64, month as i64, day as i64);
+    let a = (m - 14) / 12;
+    let jdn = (1461 * (y + 4800 + a)) / 4 + (367 * (m - 2 - 12 * a)) / 12 - (3 * ((y + 4900 + a) / 100)) / 4 + d - 32075;
+    jdn as MetricsInternalType - 2_400_001.0
+}
+
+/// Days in the given month.
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+/// TDB - TT in seconds, from the mean anomaly of the Earth (Fairhead & Bretagnon, leading terms).
+fn tdb_minus_tt(jd_tt: MetricsInternalType) -> MetricsInternalType {
+    let g = (357.53 + 0.985_600_28 * (jd_tt - J2000_JD)).to_radians();
+    0.001_657 * g.sin() + 0.000_014 * (2.0 * g).sin()
+}
+
+impl Epoch {
+    /// From a two-part JD, normalized so that `jd1` is whole and `jd2` ∈ [0, 1).
+    fn from_parts(jd1: MetricsInternalType, jd2: MetricsInternalType, scale: TimeScale) -> Self {
+        let i1 = jd1.floor();
+        let f = (jd1 - i1) + jd2;
+        let i2 = f.floor();
+        Self { jd1: i1 + i2, jd2: f - i2, scale }
+    }
+
+    /// From a Julian Date.
+    pub fn from_jd(jd: MetricsInternalType, scale: TimeScale) -> Self {
+        Self::from_parts(jd, 0.0, scale)
+    }
+
+    /// From a Modified Julian Date.
+    pub fn from_mjd(mjd: MetricsInternalType, scale: TimeScale) -> Self {
+        Self::from_parts(MJD_OFFSET, mjd, scale)
+    }
+
+    /// J2000.0, i.e. 2000-01-01 12:00 TT.
+    pub fn j2000() -> Self {
+        Self::from_jd(J2000_JD, TimeScale::Tt)
+    }
+
+    /// From a Julian epoch, e.g. `2016.0` for J2016.0 (TT, or TDB).
+    pub fn from_julian_epoch(year: MetricsInternalType, scale: TimeScale) -> Self {
+        Self::from_parts(J2000_JD, (year - 2000.0) * JULIAN_YEAR_DAYS, scale)
+    }
+
+    /// From a Besselian epoch, e.g. `1950.0` for B1950.0 (TT).
+    pub fn from_besselian_epoch(year: MetricsInternalType) -> Self {
+        Self::from_parts(2_415_020.0, 0.313_52 + (year - 1900.0) * BESSELIAN_YEAR_DAYS, TimeScale::Tt)
+    }
+
+    /// From a (proleptic Gregorian) calendar date and time of day.
+    /// 
+    /// A UTC `second` of up to 61 is accepted at 23:59 of a day that ends in a leap second. Such a day is
+    /// 86 401 s long, and UTC JDs/MJDs then count in fractions of it (as ERFA's quasi-JDs do).
+    pub fn from_calendar(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: MetricsInternalType, scale: TimeScale) -> Result<Self, AstroError> {
+        let mjd = calendar_to_mjd(year, month, day);
+        let day_seconds = match scale {
+            TimeScale::Utc => leap_seconds::utc_day_seconds(mjd),
+            _ => DAY_SECONDS
+        };
+        let max_sec = if hour == 23 && minute == 59 { 60.0 + day_seconds - DAY_SECONDS } else { 60.0 };
+        match () {
+            _ if !(1..=12).contains(&month) => Err(AstroError::OutOfDomain { what: "month", value: month as MetricsInternalType }),
+            _ if day == 0 || day > days_in_month(year, month) => Err(AstroError::OutOfDomain { what: "day", value: day as MetricsInternalType }),
+            _ if hour > 23 => Err(AstroError::OutOfDomain { what: "hour", value: hour as MetricsInternalType }),
+            _ if minute > 59 => Err(AstroError::OutOfDomain { what: "minute", value: minute as MetricsInternalType }),
+            _ if !(0.0..max_sec).contains(&second) => Err(AstroError::OutOfDomain { what: "second", value: second }),
+            _ => Ok(Self::from_parts(
+                mjd + MJD_OFFSET,
+                (hour as MetricsInternalType * 3600.0 + minute as MetricsInternalType * 60.0 + second) / day_seconds,
+                scale))
+        }
+    }
+
+    /// Julian Date.
+    pub fn jd(&self) -> MetricsInternalType { self.jd1 + self.jd2 }
+
+    /// Modified Julian Date.
+    pub fn mjd(&self) -> MetricsInternalType { (self.jd1 - MJD_OFFSET) + self.jd2 }
+
+    /// Days since J2000.0 (in `self`'s scale).
+    pub fn days_since_j2000(&self) -> MetricsInternalType { (self.jd1 - J2000_JD) + self.jd2 }
+
+    /// Julian centuries since J2000.0 (in `self`'s scale) - the `T` of most precession/nutation series.
+    pub fn centuries_since_j2000(&self) -> MetricsInternalType { self.days_since_j2000() / 36_525.0 }
+
+    /// Julian epoch, e.g. `2016.0`.
+    pub fn julian_epoch(&self) -> MetricsInternalType { 2000.0 + self.days_since_j2000() / JULIAN_YEAR_DAYS }
+
+    /// Besselian epoch, e.g. `1950.0`.
+    pub fn besselian_epoch(&self) -> MetricsInternalType {
+        1900.0 + ((self.jd1 - 2_415_020.0) + (self.jd2 - 0.313_52)) / BESSELIAN_YEAR_DAYS
+    }
+
+    /// The time scale of `self`.
+    pub fn scale(&self) -> TimeScale { self.scale }
+
+    /// Calendar date and time of day: `(year, month, day, hour, minute, second)`.
+    pub fn to_calendar(&self) -> (i32, u32, u32, u32, u32, MetricsInternalType) {
+        // Shift to a midnight-based day; Richards (2013) for the date.
+        let (d1, d2) = (self.jd1 + 0.5, self.jd2);
+        let whole = (d1.floor() + d2.floor()) as i64;
+        let frac = (d1 - d1.floor()) + (d2 - d2.floor());
+        let (whole, frac) = if frac >= 1.0 { (whole + 1, frac - 1.0) } else { (whole, frac) };
+        let f = whole + 1401 + (((4 * whole + 274_277) / 146_097) * 3) / 4 - 38;
+        let e = 4 * f + 3;
+        let h = 5 * ((e % 1461) / 4) + 2;
+        let day = (h % 153) / 5 + 1;
+        let month = ((h / 153 + 2) % 12) + 1;
+        let year = e / 1461 - 4716 + (14 - month) / 12;
+        let day_seconds = match self.scale {
+            TimeScale::Utc => leap_seconds::utc_day_seconds((whole - 2_400_001) as MetricsInternalType),
+            _ => DAY_SECONDS
+        };
+        let secs = frac * day_seconds;
+        // A leap second is 23:59:60.x, not 24:00:00.x.
+        let hour = (secs / 3600.0).floor().min(23.0);
+        let minute = ((secs - hour * 3600.0) / 60.0).floor().min(59.0);
+        (year as i32, month as u32, day as u32, hour as u32, minute as u32, secs - hour * 3600.0 - minute * 60.0)
+    }
+
+    /// `self` shifted by `seconds`, scale kept.
+    fn shifted(&self, seconds: MetricsInternalType) -> Self {
+        Self::from_parts(self.jd1, self.jd2 + seconds / DAY_SECONDS, self.scale)
+    }
+
+    /// JD of the preceding 0h, and the fraction of the day since.
+    fn midnight_split(&self) -> (MetricsInternalType, MetricsInternalType) {
+        match self.jd2 >= 0.5 {
+            true => (self.jd1 + 0.5, self.jd2 - 0.5),
+            false => (self.jd1 - 0.5, self.jd2 + 0.5)
+        }
+    }
+
+    /// `self` with `scale` relabelled.
+    fn relabelled(&self, scale: TimeScale) -> Self {
+        Self { scale, ..*self }
+    }
+
+    /// One step along UTC → TAI → TT → TDB (or back).
+    fn step(&self, towards: TimeScale) -> Self {
+        use TimeScale::*;
+        match (self.scale, towards) {
+            (Utc, _) => {
+                // ΔAT of the day's 0h holds all day, the leap second included.
+                let (midnight, frac) = self.midnight_split();
+                let mjd = midnight - MJD_OFFSET;
+                Self::from_parts(midnight, (frac * leap_seconds::utc_day_seconds(mjd) + leap_seconds::tai_minus_utc(mjd)) / DAY_SECONDS, Tai)
+            },
+            (Tai, Utc) => {
+                // ΔAT is a function of UTC: guess the UTC day, then settle on the one whose span holds `self`.
+                let (mut midnight, _) = self.shifted(-leap_seconds::tai_minus_utc(self.mjd())).midnight_split();
+                let secs = |midnight: MetricsInternalType| ((self.jd1 - midnight) + self.jd2) * DAY_SECONDS - leap_seconds::tai_minus_utc(midnight - MJD_OFFSET);
+                if secs(midnight) < 0.0 {
+                    midnight -= 1.0;
+                } else if secs(midnight) >= leap_seconds::utc_day_seconds(midnight - MJD_OFFSET) {
+                    midnight += 1.0;
+                }
+                Self::from_parts(midnight, secs(midnight) / leap_seconds::utc_day_seconds(midnight - MJD_OFFSET), Utc)
+            },
+            (Tai, _) => self.shifted(TT_MINUS_TAI).relabelled(Tt),
+            (Tt, Utc | Tai) => self.shifted(-TT_MINUS_TAI).relabelled(Tai),
+            (Tt, _) => self.shifted(tdb_minus_tt(self.jd())).relabelled(Tdb),
+            (Tdb, _) => {
+                let guess = self.shifted(-tdb_minus_tt(self.jd()));
+                self.shifted(-tdb_minus_tt(guess.jd())).relabelled(Tt)
+            },
+        }
+    }
+
+    /// `self` in the given time scale.
+    pub fn to_scale(&self, scale: TimeScale) -> Self {
+        let mut e = *self;
+        while e.scale != scale {
+            e = e.step(scale);
+        }
+        e
+    }
+
+    /// `self` as UTC.
+    pub fn utc(&self) -> Self { self.to_scale(TimeScale::Utc) }
+    /// `self` as TAI.
+    pub fn tai(&self) -> Self { self.to_scale(TimeScale::Tai) }
+    /// `self` as TT.
+    pub fn tt(&self) -> Self { self.to_scale(TimeScale::Tt) }
+    /// `self` as TDB.
+    pub fn tdb(&self) -> Self { self.to_scale(TimeScale::Tdb) }
+
+    /// Scale in which to do elapsed-time arithmetic: UTC days aren't all equally long.
+    fn uniform(&self) -> Self {
+        match self.scale {
+            TimeScale::Utc => self.tai(),
+            _ => *self
+        }
+    }
+}
+
+impl Add<Time> for Epoch {
+    type Output = Epoch;
+    /// `self + duration`; UTC epochs step over leap seconds correctly.
+    fn add(self, rhs: Time) -> Self::Output {
+        self.uniform().shifted(rhs.s().raw()).to_scale(self.scale)
+    }
+}
+
+impl Sub<Time> for Epoch {
+    type Output = Epoch;
+    fn sub(self, rhs: Time) -> Self::Output {
+        self + Time::S(-rhs.s().raw())
+    }
+}
+
+impl Sub for Epoch {
+    type Output = Time;
+    /// Elapsed time from `rhs` to `self`, in days.
+    fn sub(self, rhs: Self) -> Self::Output {
+        let a = self.uniform();
+        let b = rhs.to_scale(a.scale);
+        Time::D((a.jd1 - b.jd1) + (a.jd2 - b.jd2))
+    }
+}
+
+impl PartialEq for Epoch {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Epoch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (*self - *other).raw().partial_cmp(&0.0)
+    }
+}
+
+impl Display for Epoch {
+    /// ISO 8601-ish, e.g. "2000-01-01T12:00:00.000 TT"; `{:.n}` sets the decimals of seconds.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let p = f.precision().unwrap_or(3);
+        // Round first so that 59.9996 s won't print as "60.000".
+        let rounded = Self::from_parts(self.jd1, self.jd2 + 0.5 * (10.0 as MetricsInternalType).powi(-(p as i32)) / DAY_SECONDS, self.scale);
+        let (y, mo, d, h, mi, s) = rounded.to_calendar();
+        let s = (s * (10.0 as MetricsInternalType).powi(p as i32)).floor() / (10.0 as MetricsInternalType).powi(p as i32);
+        let w = if p > 0 { p + 3 } else { 2 };
+        write!(f, "{y:04}-{mo:02}-{d:02}T{h:02}:{mi:02}:{s:0w$.p$} {}", self.scale.name())
+    }
+}
+
+#[cfg(test)]
+mod epoch_tests {
+    use crate::{AsTime, ApproxEq, Tolerance};
+    use super::*;
+
+    #[test]
+    fn julian_dates() {
+        let j2000 = Epoch::from_calendar(2000, 1, 1, 12, 0, 0.0, TimeScale::Tt).unwrap();
+        assert_eq!(J2000_JD, j2000.jd());
+        assert_eq!(51544.5, j2000.mjd());
+        assert_eq!(2000.0, j2000.julian_epoch());
+        assert_eq!(Epoch::j2000(), Epoch::from_julian_epoch(2000.0, TimeScale::Tt));
+        assert!(Epoch::from_besselian_epoch(1950.0).jd().approx_eq(&2_433_282.423_459_05, Tolerance::absolute(1e-6)));
+        assert!(Epoch::from_besselian_epoch(1950.0).besselian_epoch().approx_eq(&1950.0, Tolerance::absolute(1e-12)));
+        assert_eq!((2000, 1, 1, 12, 0, 0.0), j2000.to_calendar());
+        assert_eq!((1999, 12, 31, 0, 0, 0.0), Epoch::from_mjd(51543.0, TimeScale::Utc).to_calendar());
+        assert!(Epoch::from_calendar(2023, 2, 29, 0, 0, 0.0, TimeScale::Utc).is_err());
+    }
+
+    #[test]
+    fn leap_second_only_when_due() {
+        let leap = Epoch::from_calendar(2016, 12, 31, 23, 59, 60.5, TimeScale::Utc).unwrap();
+        let next = Epoch::from_calendar(2017, 1, 1, 0, 0, 0.5, TimeScale::Utc).unwrap();
+        // TAI - UTC is still 36 s during the leap second itself.
+        assert_eq!("2017-01-01T00:00:36.500 TAI", leap.tai().to_string());
+        assert_eq!("2017-01-01T00:00:37.500 TAI", next.tai().to_string());
+        assert!((next - leap).s().approx_eq(&1.s(), Tolerance::absolute(1e-6)));
+        assert_eq!("2016-12-31T23:59:60.500 UTC", leap.to_string());
+        assert_eq!("2016-12-31T23:59:60.500 UTC", leap.tai().utc().to_string());
+        assert!(Epoch::from_calendar(2023, 3, 1, 23, 59, 60.5, TimeScale::Utc).is_err());
+        assert!(Epoch::from_calendar(2016, 12, 31, 23, 58, 60.0, TimeScale::Utc).is_err());
+        assert!(Epoch::from_calendar(2016, 12, 31, 23, 59, 60.0, TimeScale::Tt).is_err());
+    }
+
+    #[test]
+    fn time_scales() {
+        // J2000.0 TT is 11:58:55.816 UTC.
+        let utc = Epoch::j2000().utc();
+        assert_eq!("2000-01-01T11:58:55.816 UTC", utc.to_string());
+        assert_eq!("2000-01-01T12:00:00.000 TT", utc.tt().to_string());
+        let tdb = Epoch::j2000().tdb();
+        assert!(((tdb - Epoch::j2000()).s().raw()).abs() < 0.002);
+        assert!((tdb.tt() - Epoch::j2000()).s().raw().abs() < 1e-6);
+    }
+
+    #[test]
+    fn arithmetic() {
+        // 2016-12-31T23:59:59 UTC + 2 s lands on 2017-01-01T00:00:00 - the leap second is in between.
+        let e = Epoch::from_calendar(2016, 12, 31, 23, 59, 59.0, TimeScale::Utc).unwrap();
+        assert_eq!("2017-01-01T00:00:00.000 UTC", (e + 2.s()).to_string());
+        let j = Epoch::j2000() + 10.yr();
+        assert_eq!(2010.0, j.julian_epoch());
+        assert!((j - Epoch::j2000()).approx_eq(&3652.5.d(), Tolerance::default()));
+        assert!(j > Epoch::j2000());
+        assert_eq!(Epoch::j2000(), j - 10.yr());
+    }
+}
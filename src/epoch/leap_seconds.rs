@@ -0,0 +1,45 @@
+//! TAI - UTC, per IERS Bulletin C. Embedded - no fetching anything from anywhere.
+//! 
+//! Mind to add new leap seconds here as the IERS announces them - the latest one was at the end of 2016.
+use crate::{DAY_SECONDS, MetricsInternalType};
+
+use super::calendar_to_mjd;
+
+/// Year and month from the start of which TAI - UTC is the given number of seconds.
+const LEAP_SECONDS: [(i32, u32, MetricsInternalType); 28] = [
+    (1972, 1, 10.0), (1972, 7, 11.0), (1973, 1, 12.0), (1974, 1, 13.0), (1975, 1, 14.0), (1976, 1, 15.0),
+    (1977, 1, 16.0), (1978, 1, 17.0), (1979, 1, 18.0), (1980, 1, 19.0), (1981, 7, 20.0), (1982, 7, 21.0),
+    (1983, 7, 22.0), (1985, 7, 23.0), (1988, 1, 24.0), (1990, 1, 25.0), (1991, 1, 26.0), (1992, 7, 27.0),
+    (1993, 7, 28.0), (1994, 7, 29.0), (1996, 1, 30.0), (1997, 7, 31.0), (1999, 1, 32.0), (2006, 1, 33.0),
+    (2009, 1, 34.0), (2012, 7, 35.0), (2015, 7, 36.0), (2017, 1, 37.0),
+];
+
+/// TAI - UTC in seconds at the given UTC MJD.
+/// 
+/// Pre-1972 UTC (with its rubber seconds) isn't modelled - the 1972 value of 10 s is used instead.
+pub(super) fn tai_minus_utc(mjd_utc: MetricsInternalType) -> MetricsInternalType {
+    LEAP_SECONDS.iter().rev()
+        .find(|(y, m, _)| mjd_utc >= calendar_to_mjd(*y, *m, 1))
+        .map_or(LEAP_SECONDS[0].2, |(_, _, dat)| *dat)
+}
+
+/// Length in SI seconds of the UTC day starting at the given MJD (0h) - 86 401 s if it ends in a leap second.
+pub(super) fn utc_day_seconds(mjd_utc: MetricsInternalType) -> MetricsInternalType {
+    DAY_SECONDS + tai_minus_utc(mjd_utc + 1.0) - tai_minus_utc(mjd_utc)
+}
+
+#[cfg(test)]
+mod leap_seconds_tests {
+    use super::*;
+
+    #[test]
+    fn table() {
+        assert_eq!(41317.0, calendar_to_mjd(1972, 1, 1));
+        assert_eq!(57754.0, calendar_to_mjd(2017, 1, 1));
+        assert_eq!(36.0, tai_minus_utc(57753.999));
+        assert_eq!(37.0, tai_minus_utc(57754.0));
+        assert_eq!(10.0, tai_minus_utc(30000.0));
+        assert_eq!(86_401.0, utc_day_seconds(57753.0));
+        assert_eq!(86_400.0, utc_day_seconds(57754.0));
+    }
+}
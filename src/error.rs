@@ -0,0 +1,69 @@
+//! Crate-wide error type.
+use std::fmt::Display;
+
+use crate::{ConversionError, Mass, MetricsInternalType, ParseMetricError, QuantityError, SpatialUnit, Temperature};
+
+/// Everything that can go wrong within astrometrics.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AstroError {
+    /// Not-a-number, e.g. [Temperature::X] or `0/0`.
+    NaN { metric: &'static str },
+    /// ±∞ where a finite value is expected.
+    Infinite { metric: &'static str },
+    /// Mass below zero.
+    NegativeMass(Mass),
+    /// Length/distance/radius below zero.
+    NegativeLength(SpatialUnit),
+    /// Colder than cold can be.
+    BelowAbsoluteZero(Temperature),
+    /// Division by zero.
+    DivisionByZero,
+    /// A value out of its valid domain, e.g. an eccentricity of -1.
+    OutOfDomain { what: &'static str, value: MetricsInternalType },
+    /// See [QuantityError].
+    Quantity(QuantityError),
+    /// See [ParseMetricError].
+    Parse(ParseMetricError),
+    /// See [ConversionError].
+    Conversion(ConversionError),
+}
+
+impl Display for AstroError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NaN { metric } => write!(f, "{metric} is NaN"),
+            Self::Infinite { metric } => write!(f, "{metric} is infinite"),
+            Self::NegativeMass(m) => write!(f, "negative mass: {m}"),
+            Self::NegativeLength(s) => write!(f, "negative length: {s}"),
+            Self::BelowAbsoluteZero(t) => write!(f, "below absolute zero: {t}"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+            Self::OutOfDomain { what, value } => write!(f, "{what} out of domain: {value}"),
+            Self::Quantity(e) => e.fmt(f),
+            Self::Parse(e) => e.fmt(f),
+            Self::Conversion(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for AstroError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Quantity(e) => Some(e),
+            Self::Parse(e) => Some(e),
+            Self::Conversion(e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+impl From<QuantityError> for AstroError {
+    fn from(value: QuantityError) -> Self { Self::Quantity(value) }
+}
+
+impl From<ParseMetricError> for AstroError {
+    fn from(value: ParseMetricError) -> Self { Self::Parse(value) }
+}
+
+impl From<ConversionError> for AstroError {
+    fn from(value: ConversionError) -> Self { Self::Conversion(value) }
+}
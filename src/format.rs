@@ -0,0 +1,265 @@
+//! Formatting
+//! 
+//! Configurable output for the metrics - precision, significant figures, scientific notation,
+//! Unicode vs ASCII symbols and value/unit spacing.
+//! 
+//! The metrics' `Display` (and `LowerExp`/`UpperExp`) impls route through here too, so the usual
+//! `{:.3}`, `{:e}`, `{:>12}` specifiers work, and `{:#}` switches to ASCII symbols.
+use std::fmt::{Display, Formatter};
+
+use crate::MetricsInternalType;
+
+/// How many digits to show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Digits {
+    /// Whatever the unit deems sensible.
+    #[default]
+    UnitDefault,
+    /// Fixed number of decimals.
+    Decimals(usize),
+    /// Number of significant figures.
+    Significant(usize),
+}
+
+/// Plain or scientific notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Notation {
+    #[default]
+    Fixed,
+    /// E.g. `1.99e30 kg`.
+    Scientific,
+    /// E.g. `1.99E30 kg`.
+    ScientificUpper,
+}
+
+/// Which unit symbols to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symbols {
+    /// M☉, R⊕, ⁰C, …
+    #[default]
+    Unicode,
+    /// Msun, Rearth, degC, … - all of which `FromStr` accepts, too.
+    Ascii,
+}
+
+/// What goes between the value and the unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Spacing {
+    /// Whatever the unit has traditionally used.
+    #[default]
+    UnitDefault,
+    /// `5778K`
+    Tight,
+    /// `5778 K`
+    Spaced,
+}
+
+/// Formatting options for [FormatMetric].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    pub digits: Digits,
+    pub notation: Notation,
+    pub symbols: Symbols,
+    pub spacing: Spacing,
+}
+
+impl FormatOptions {
+    /// Use `n` decimals.
+    pub fn decimals(mut self, n: usize) -> Self {
+        self.digits = Digits::Decimals(n);
+        self
+    }
+
+    /// Use `n` significant figures.
+    pub fn significant(mut self, n: usize) -> Self {
+        self.digits = Digits::Significant(n);
+        self
+    }
+
+    /// Use scientific notation.
+    pub fn scientific(mut self) -> Self {
+        self.notation = Notation::Scientific;
+        self
+    }
+
+    /// Use ASCII unit symbols.
+    pub fn ascii(mut self) -> Self {
+        self.symbols = Symbols::Ascii;
+        self
+    }
+
+    /// Set value/unit spacing.
+    pub fn spacing(mut self, spacing: Spacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Options as requested by a [Formatter] - precision, `#` for ASCII.
+    fn from_formatter(f: &Formatter<'_>, notation: Notation) -> Self {
+        Self {
+            digits: f.precision().map_or(Digits::UnitDefault, Digits::Decimals),
+            notation,
+            symbols: if f.alternate() { Symbols::Ascii } else { Symbols::Unicode },
+            spacing: Spacing::UnitDefault,
+        }
+    }
+}
+
+/// The bits a metric exposes to get formatted.
+pub trait FormatMetric {
+    /// Value to show, or `None` for value-less things like black holes.
+    fn format_value(&self) -> Option<MetricsInternalType>;
+    /// Unit symbol.
+    fn format_symbol(&self, symbols: Symbols) -> &'static str;
+    /// Decimals to use unless told otherwise.
+    fn default_decimals(&self) -> usize;
+    /// Whether the unit traditionally has a space in front of it.
+    fn default_spaced(&self) -> bool { true }
+
+    /// Wrap `self` for formatting with the given `options`.
+    fn formatted(&self, options: FormatOptions) -> Formatted<'_, Self> where Self: Sized {
+        Formatted { metric: self, options }
+    }
+
+    /// Format `self` into a [String] with the given `options`.
+    fn format_with(&self, options: FormatOptions) -> String where Self: Sized {
+        self.formatted(options).to_string()
+    }
+}
+
+/// A metric bundled with its [FormatOptions], ready to be `Display`ed.
+#[derive(Debug, Clone, Copy)]
+pub struct Formatted<'a, T: FormatMetric> {
+    metric: &'a T,
+    options: FormatOptions,
+}
+
+impl<T: FormatMetric> Display for Formatted<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        pad(f, &render(self.metric, &self.options))
+    }
+}
+
+/// Render `v` with `n` significant figures in fixed notation.
+fn significant_fixed(v: MetricsInternalType, n: usize) -> String {
+    if v == 0.0 || !v.is_finite() {
+        return format!("{:.*}", n.saturating_sub(1), v);
+    }
+    // Take the exponent after rounding to `n` figures, so that e.g. 9.99 → 1.0e1 moves up a power of ten.
+    let n = n.max(1);
+    let sci = format!("{:.*e}", n - 1, v);
+    let (mantissa, exponent) = sci.split_once('e').expect("`{:e}` always has an exponent");
+    let decimals = n as i32 - 1 - exponent.parse::<i32>().expect("`{:e}` exponent is an integer");
+    if decimals >= 0 {
+        format!("{:.*}", decimals as usize, v)
+    } else {
+        // Pad the rounded mantissa with zeros - multiplying back out would print the float's binary expansion.
+        format!("{}{}", mantissa.replace('.', ""), "0".repeat(-decimals as usize))
+    }
+}
+
+/// Render `metric` according to `options`.
+fn render<T: FormatMetric>(metric: &T, options: &FormatOptions) -> String {
+    let symbol = metric.format_symbol(options.symbols);
+    let Some(v) = metric.format_value() else {
+        return symbol.to_string();
+    };
+    let value = match (options.notation, options.digits) {
+        (Notation::Fixed, Digits::UnitDefault) => format!("{:.*}", metric.default_decimals(), v),
+        (Notation::Fixed, Digits::Decimals(n)) => format!("{:.*}", n, v),
+        (Notation::Fixed, Digits::Significant(n)) => significant_fixed(v, n),
+        (_, Digits::UnitDefault) => format!("{:e}", v),
+        (_, Digits::Decimals(n)) => format!("{:.*e}", n, v),
+        (_, Digits::Significant(n)) => format!("{:.*e}", n.saturating_sub(1), v),
+    };
+    let value = match options.notation {
+        Notation::ScientificUpper => value.replace('e', "E"),
+        _ => value
+    };
+    let spaced = match options.spacing {
+        Spacing::UnitDefault => metric.default_spaced(),
+        Spacing::Tight => false,
+        Spacing::Spaced => true,
+    };
+    if spaced {
+        format!("{value} {symbol}")
+    } else {
+        format!("{value}{symbol}")
+    }
+}
+
+/// Pad `s` per the formatter's width/fill/alignment.
+/// 
+/// [Formatter::pad] would treat precision as max. width, which is not what we want here.
+fn pad(f: &mut Formatter<'_>, s: &str) -> std::fmt::Result {
+    let len = s.chars().count();
+    let Some(width) = f.width().filter(|w| *w > len) else {
+        return f.write_str(s);
+    };
+    let fill = f.fill().to_string();
+    let (pre, post) = match f.align() {
+        Some(std::fmt::Alignment::Left) => (0, width - len),
+        Some(std::fmt::Alignment::Center) => ((width - len) / 2, width - len - (width - len) / 2),
+        // Numbers-ish, so right-aligned by default.
+        _ => (width - len, 0),
+    };
+    write!(f, "{}{s}{}", fill.repeat(pre), fill.repeat(post))
+}
+
+/// [Display] on behalf of a metric.
+pub(crate) fn display<T: FormatMetric>(metric: &T, f: &mut Formatter<'_>) -> std::fmt::Result {
+    pad(f, &render(metric, &FormatOptions::from_formatter(f, Notation::Fixed)))
+}
+
+/// [std::fmt::LowerExp] on behalf of a metric.
+pub(crate) fn lower_exp<T: FormatMetric>(metric: &T, f: &mut Formatter<'_>) -> std::fmt::Result {
+    pad(f, &render(metric, &FormatOptions::from_formatter(f, Notation::Scientific)))
+}
+
+/// [std::fmt::UpperExp] on behalf of a metric.
+pub(crate) fn upper_exp<T: FormatMetric>(metric: &T, f: &mut Formatter<'_>) -> std::fmt::Result {
+    pad(f, &render(metric, &FormatOptions::from_formatter(f, Notation::ScientificUpper)))
+}
+
+/// `Display`, `LowerExp` and `UpperExp` for a [FormatMetric] implementor.
+macro_rules! display_via_format {
+    ($metric:ident) => {
+        impl std::fmt::Display for $metric {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { $crate::format::display(self, f) }
+        }
+        impl std::fmt::LowerExp for $metric {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { $crate::format::lower_exp(self, f) }
+        }
+        impl std::fmt::UpperExp for $metric {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { $crate::format::upper_exp(self, f) }
+        }
+    };
+}
+pub(crate) use display_via_format;
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+
+    #[test]
+    fn significant_figures() {
+        assert_eq!("1.23", significant_fixed(1.2345, 3));
+        assert_eq!("0.00123", significant_fixed(0.0012345, 3));
+        assert_eq!("12300", significant_fixed(12345.0, 3));
+        assert_eq!("-4.0", significant_fixed(-4.0, 2));
+        assert_eq!("12300000000000000000000000", significant_fixed(1.23456e25, 3));
+        assert_eq!("100000000000000000000000", significant_fixed(1e23, 2));
+        assert_eq!("-1000", significant_fixed(-999.7, 2));
+        // Rounding up across a power of ten must not add a figure.
+        assert_eq!("10", significant_fixed(9.99, 2));
+        assert_eq!("1.0", significant_fixed(0.999, 2));
+        assert_eq!("-100", significant_fixed(-99.96, 3));
+        assert_eq!("100000", significant_fixed(99_960.0, 3));
+    }
+
+    #[test]
+    fn significant_large_metric() {
+        use crate::AsMass;
+        assert_eq!("12300000000000000000000000 kg", 1.23456e25.kg().format_with(FormatOptions::default().significant(3)).to_string());
+    }
+}
@@ -0,0 +1,133 @@
+//! Humanize
+//! 
+//! Re-express a metric in whichever unit reads most naturally - 4.2e16 m → 4.44 ly,
+//! 0.003 M☉ → 3.14 M♃, and so on.
+use crate::{DefoAble, Mass, MetricsInternalType, SpatialUnit, UnitTable};
+
+/// Rules for picking the "best" unit.
+/// 
+/// The chosen unit is the largest allowed one in which the value is at least [threshold][HumanizeRules::threshold].
+#[derive(Debug, Clone, Copy)]
+pub struct HumanizeRules {
+    /// Smallest acceptable (absolute) value in the chosen unit. Default `1.0`.
+    pub threshold: MetricsInternalType,
+    /// Use parsecs instead of light-years.
+    pub prefer_pc: bool,
+    /// Consider R⊕ and R☉, too. They're radii, mostly, so off by default.
+    pub use_radii: bool,
+    /// Largest spatial unit allowed, e.g. `SpatialUnit::Au(…)` within a star system.
+    /// The variant's value is irrelevant.
+    pub max_spatial: Option<SpatialUnit>,
+    /// Largest mass unit allowed. The variant's value is irrelevant.
+    pub max_mass: Option<Mass>,
+}
+
+impl Default for HumanizeRules {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            prefer_pc: false,
+            use_radii: false,
+            max_spatial: None,
+            max_mass: None,
+        }
+    }
+}
+
+impl HumanizeRules {
+    /// Prefer parsecs over light-years.
+    pub fn prefer_pc(mut self) -> Self {
+        self.prefer_pc = true;
+        self
+    }
+
+    /// Consider celestial radii (R⊕, R☉) as well.
+    pub fn use_radii(mut self) -> Self {
+        self.use_radii = true;
+        self
+    }
+
+    /// Never go beyond the given spatial unit.
+    pub fn cap_spatial(mut self, unit: SpatialUnit) -> Self {
+        self.max_spatial = Some(unit);
+        self
+    }
+
+    /// Never go beyond the given mass unit.
+    pub fn cap_mass(mut self, unit: Mass) -> Self {
+        self.max_mass = Some(unit);
+        self
+    }
+}
+
+/// A trait for anything that can be re-expressed in a "best" unit.
+pub trait Humanize: Sized {
+    /// `self` in the most natural unit, per default [HumanizeRules].
+    fn humanize(&self) -> Self {
+        self.humanize_with(&HumanizeRules::default())
+    }
+
+    /// `self` in the most natural unit, per given `rules`.
+    fn humanize_with(&self, rules: &HumanizeRules) -> Self;
+}
+
+/// Pick the first of `candidates` (biggest first) where the value reaches the threshold,
+/// or the last (smallest) one if none does.
+fn pick<T: DefoAble + Copy>(value: &T, candidates: &[T], threshold: MetricsInternalType) -> T {
+    let raw = value.raw();
+    if raw == 0.0 || !raw.is_finite() || candidates.is_empty() {
+        return *value;
+    }
+    candidates.iter()
+        .map(|c| value.cnv_into(c))
+        .find(|c| c.raw().abs() >= threshold)
+        .unwrap_or_else(|| value.cnv_into(&candidates[candidates.len() - 1]))
+}
+
+impl Humanize for SpatialUnit {
+    fn humanize_with(&self, rules: &HumanizeRules) -> Self {
+        let candidates: Vec<SpatialUnit> = Self::UNITS.iter().rev()
+            .map(|(u, _)| *u)
+            .filter(|u| !matches!(u, SpatialUnit::Pc(_) if !rules.prefer_pc))
+            .filter(|u| !matches!(u, SpatialUnit::Ly(_) if rules.prefer_pc))
+            .filter(|u| rules.use_radii || !matches!(u, SpatialUnit::RE(_) | SpatialUnit::RO(_)))
+            .filter(|u| rules.max_spatial.is_none_or(|max| u.rank() <= max.rank()))
+            .collect();
+        pick(self, &candidates, rules.threshold)
+    }
+}
+
+impl Humanize for Mass {
+    fn humanize_with(&self, rules: &HumanizeRules) -> Self {
+        let candidates: Vec<Mass> = Self::UNITS.iter().rev()
+            .map(|(u, _)| *u)
+            .filter(|u| rules.max_mass.is_none_or(|max| u.rank() <= max.rank()))
+            .collect();
+        pick(self, &candidates, rules.threshold)
+    }
+}
+
+#[cfg(test)]
+mod humanize_tests {
+    use crate::{AsMass, AsSpatialUnit};
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let d = 4.2e16.m().humanize();
+        assert!(matches!(d, SpatialUnit::Ly(v) if (v - 4.44).abs() < 0.01));
+        assert!(matches!(4.2e16.m().humanize_with(&HumanizeRules::default().prefer_pc()), SpatialUnit::Pc(_)));
+        assert!(matches!(0.5.ly().humanize_with(&HumanizeRules::default().cap_spatial(SpatialUnit::Au(0.0))), SpatialUnit::Au(_)));
+        assert!(matches!(1e3.m().humanize(), SpatialUnit::M(_)));
+        assert!(matches!(7e9.m().humanize_with(&HumanizeRules::default().use_radii()), SpatialUnit::RO(_)));
+    }
+
+    #[test]
+    fn masses() {
+        assert!(matches!(0.003.mo().humanize(), Mass::MJ(v) if (v - 3.1).abs() < 0.05));
+        assert!(matches!(1e25.kg().humanize(), Mass::ME(v) if (v - 1.67).abs() < 0.01));
+        assert!(matches!(1e25.kg().humanize_with(&HumanizeRules::default().cap_mass(Mass::Kg(0.0))), Mass::Kg(_)));
+        assert!(matches!(500.g().humanize(), Mass::G(_)));
+        assert!(matches!(0.0.mo().humanize(), Mass::MO(_)));
+    }
+}
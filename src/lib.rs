@@ -1,21 +1,83 @@
+// [Constants]
+pub mod constants;
+pub use constants::{Constant, ConstantSet};
+// [Unit tables]
+mod units;
+pub use units::UnitTable;
 // [Mass]
 mod mass;
 pub use mass::{Mass, AsMass};
 // [Temperature]
 mod temperature;
-pub use temperature::{Temperature, AsTemperature};
+pub use temperature::{Temperature, AsTemperature, ABS_ZERO};
 // [Spatial]
 mod spatial;
-pub use spatial::{AsSpatialUnit, SpatialUnit, iau::*};
+pub use spatial::{AsSpatialUnit, AsCelestialRadii, SpatialUnit, SpatialVector, Megastructure, SpatialContained, iau::*};
+// [Time]
+mod time;
+pub use time::{Time, AsTime, DAY_SECONDS, JULIAN_YEAR_DAYS, JULIAN_YEAR_SECONDS, SIDEREAL_YEAR_DAYS, TROPICAL_YEAR_DAYS};
+// [Epochs]
+mod epoch;
+pub use epoch::{Epoch, TimeScale, J2000_JD, MJD_OFFSET};
+// [Derived]
+mod derived;
+pub use derived::{Area, AsArea, Volume, AsVolume, Density, AsDensity, Velocity, AsVelocity, Acceleration, AsAcceleration, STANDARD_GRAVITY, Luminosity, AsLuminosity};
+// [Angle]
+mod angle;
+pub use angle::{Angle, AsAngle, Sexagesimal};
+// [Parallax]
+mod parallax;
+pub use parallax::{Parallax, DistanceEstimator, DistanceEstimate, EDSD_LENGTH_SCALE_KPC};
+// [Coordinates]
+mod coords;
+pub use coords::{SkyCoord, Frame, CrossMatch, SkyIndex, cross_match, nutation, obliquity};
+// [Proper motion]
+mod motion;
+pub use motion::{Astrometry, ProperMotion, Uvw};
+// [Apparent places]
+mod apparent;
+// [Orbits]
+mod orbit;
+pub use orbit::{Orbit, RocheModel, hill_radius, kepler_period, roche_limit, roche_limit_from_masses, solve_kepler, sphere_of_influence};
+// [Magnitude]
+mod magnitude;
+pub use magnitude::{Magnitude, L0_WATTS, bolometric_correction, distance_modulus};
+// [Parsing]
+mod parse;
+pub use parse::{ParseMetricError, ParseMetricErrorKind};
+// [Formatting]
+pub mod format;
+pub use format::{FormatMetric, FormatOptions};
+// [Humanize]
+mod humanize;
+pub use humanize::{Humanize, HumanizeRules};
+// [Conversions]
+pub mod convert;
+pub use convert::{ConversionError, OverflowPolicy, set_overflow_policy, overflow_policy};
+// [Errors & checks]
+mod error;
+pub use error::AstroError;
+mod checked;
+pub use checked::Checked;
+// [Approx. equality]
+mod approx;
+pub use approx::{ApproxEq, Tolerance};
+// [Quantity]
+mod quantity;
+pub use quantity::{Quantity, QuantityError, Dimension, BaseDimension};
 
 // Whenever 'f128' is stable, we're ready for it.
 #[cfg(not(feature = "f128_stable"))]
 type MetricsInternalType = f64;
 #[cfg(feature = "f128_stable")]
 type MetricsInternalType = f128;
+#[cfg(not(feature = "f128_stable"))]
+pub(crate) use std::f64::consts::{PI, TAU};
+#[cfg(feature = "f128_stable")]
+pub(crate) use std::f128::consts::{PI, TAU};
 
 #[macro_export]
-/// `From<$metric>` for $some_primitive.
+/// `From<$metric>` for float primitives, `TryFrom<$metric>` for integer ones.
 /// 
 /// `PartialEq<$some_primitive>` for `$metric` and the other way 'round.
 /// 
@@ -49,14 +111,39 @@ macro_rules! defo {
     };
     (@impl_f $bits:tt; $metric:ident) => {paste!{
         defo!(@impl_it [<f $bits>]; $metric);
+        defo!(@from_f [<f $bits>]; $metric);
     }};
 
     // Integerishers…
     (@ints [$($bits:tt),+]; $metric:ident) => {paste!{$(
         defo!(@impl_it [<u $bits>]; $metric);
         defo!(@impl_it [<i $bits>]; $metric);
+        defo!(@try_from_i [<u $bits>]; $metric);
+        defo!(@try_from_i [<i $bits>]; $metric);
     )*}};
 
+    // Conversions from $metric into primitives…
+    // …floats can take it all (give or take some precision),
+    (@from_f $typ:ident; $metric:ident) => {
+        impl From<&$metric> for $typ { fn from(value: &$metric) -> $typ { value.raw() as $typ }}
+        impl From<$metric> for $typ { fn from(value: $metric) -> $typ {<$typ as From<&$metric>>::from(&value)}}
+    };
+    // …integers not so much. See [OverflowPolicy].
+    (@try_from_i $typ:ident; $metric:ident) => {
+        impl TryFrom<&$metric> for $typ {
+            type Error = $crate::ConversionError;
+            fn try_from(value: &$metric) -> Result<$typ, Self::Error> {
+                let v = value.raw();
+                $crate::convert::check_int_range(v, $typ::MIN as MetricsInternalType, $typ::MAX as MetricsInternalType, stringify!($metric), stringify!($typ))?;
+                Ok(v as $typ)
+            }
+        }
+        impl TryFrom<$metric> for $typ {
+            type Error = $crate::ConversionError;
+            fn try_from(value: $metric) -> Result<$typ, Self::Error> {<$typ as TryFrom<&$metric>>::try_from(&value)}
+        }
+    };
+
     // Calculus…
     (@calc $typ:ident [$(($trait:ident, $fn:ident)),+]; $metric:ident ) => {$(
         defo!(@calc_t $typ $trait $fn; $metric);
@@ -115,9 +202,10 @@ macro_rules! defo {
         }
     };
 
-    // Define calc for core $metric itself
+    // Define calc for core $metric itself. `Mul` and `Div` between metrics are
+    // dimension-aware and thus live in [dimensional] instead.
     (@metric $metric:ident) => {
-        defo!(@calc_m [(Add, add), (Sub, sub), (Div, div), (Mul, mul)]; $metric);
+        defo!(@calc_m [(Add, add), (Sub, sub)]; $metric);
     };
     (@calc_m [$(($trait:ident, $fn:ident)),+]; $metric:ident) => {
         $(defo!(@calc_m_t $trait $fn; $metric);)*
@@ -150,15 +238,6 @@ macro_rules! defo {
     //
     (@impl_it $typ:ident; $metric:ident) => {paste!{
         defo!(@calc $typ [(Add, add), (Sub, sub), (Div, div), (Mul, mul)]; $metric);
-        
-        impl From<&$metric> for $typ { fn from(value: &$metric) -> $typ {
-            let v = value.raw();
-            if v > $typ::MAX as MetricsInternalType {
-                log::warn!("Contained raw value '{v}' of the {} exceeds the capacity of a `{}`!", stringify!($metric), stringify!($typ));
-            }
-            v as $typ
-        }}
-        impl From<$metric> for $typ { fn from(value: $metric) -> $typ {<$typ as From<&$metric>>::from(&value)}}
 
         impl PartialEq<$typ> for $metric { fn eq(&self, other: &$typ) -> bool {self.raw().total_cmp(&(*other as MetricsInternalType)) == Ordering::Equal}}
         impl PartialEq<$metric> for $typ { fn eq(&self, other: &$metric) -> bool { (*self as MetricsInternalType).total_cmp(&other.raw()) == Ordering::Equal }}
@@ -172,9 +251,76 @@ macro_rules! defo {
     }};
 }
 
-/// Generic ratio calc.
-const fn ratio(num: MetricsInternalType, denom: MetricsInternalType) -> MetricsInternalType {
-    num / denom
+/// Macro to define `As…` impls for a variety of primitives.
+macro_rules! define_as_for_prim {
+    ($as:ident => $metric:ident $fns:tt; f [$($f_bits:tt),+], int [$($i_bits:tt),+]) => {
+        $(define_as_for_prim!(@f $f_bits; $as => $metric $fns);)*
+        $(define_as_for_prim!(@int $i_bits; $as => $metric $fns);)*
+    };
+    // f128 special case - drop when f128 is stable enough (and/or hardwarewise useable).
+    (@f 128; $as:ident => $metric:ident $fns:tt) => {
+        #[cfg(feature = "f128_stable")]
+        define_as_for_prim!(@f_actual 128; $as => $metric $fns);
+    };
+    (@f $bits:tt; $as:ident => $metric:ident $fns:tt) => {
+        define_as_for_prim!(@f_actual $bits; $as => $metric $fns);
+    };
+    (@f_actual $bits:tt; $as:ident => $metric:ident [$($fn:ident => $variant:ident),+]) => {paste!{
+        impl $as for [<f $bits>] {$(
+            fn $fn(&self) -> $metric { $metric::$variant(*self as MetricsInternalType) }
+        )*}
+    }};
+    (@int $bits:tt; $as:ident => $metric:ident [$($fn:ident => $variant:ident),+]) => {paste!{
+        // unsigned
+        impl $as for [<u $bits>] {$(
+            fn $fn(&self) -> $metric { (*self as MetricsInternalType).$fn() }
+        )*}
+        // signed
+        impl $as for [<i $bits>] {$(
+            fn $fn(&self) -> $metric { (*self as MetricsInternalType).$fn() }
+        )*}
+    }};
+}
+pub(crate) use define_as_for_prim;
+
+#[macro_export]
+/// Dimension-aware `$lhs ⊙ $rhs → $out` for owned, borrowed and mixed operands.
+/// 
+/// The `|a, b|` closure-ish body gets `&$lhs` and `&$rhs`.
+/// 
+/// # Examples
+/// 
+/// ```text
+/// dimensional!(SpatialUnit * SpatialUnit => Area, |a, b| Area::M2(a.m().raw() * b.m().raw()));
+/// ```
+macro_rules! dimensional {
+    ($lhs:ident * $rhs:ident => $out:ty, |$a:ident, $b:ident| $body:expr) => {
+        dimensional!(@impl Mul mul $lhs $rhs $out, |$a, $b| $body);
+    };
+    ($lhs:ident / $rhs:ident => $out:ty, |$a:ident, $b:ident| $body:expr) => {
+        dimensional!(@impl Div div $lhs $rhs $out, |$a, $b| $body);
+    };
+    (@impl $trait:ident $fn:ident $lhs:ident $rhs:ident $out:ty, |$a:ident, $b:ident| $body:expr) => {
+        impl std::ops::$trait<&$rhs> for &$lhs {
+            type Output = $out;
+            fn $fn(self, rhs: &$rhs) -> Self::Output {
+                let ($a, $b) = (self, rhs);
+                $body
+            }
+        }
+        impl std::ops::$trait<$rhs> for &$lhs {
+            type Output = $out;
+            fn $fn(self, rhs: $rhs) -> Self::Output {<&$lhs as std::ops::$trait<&$rhs>>::$fn(self, &rhs)}
+        }
+        impl std::ops::$trait<&$rhs> for $lhs {
+            type Output = $out;
+            fn $fn(self, rhs: &$rhs) -> Self::Output {<&$lhs as std::ops::$trait<&$rhs>>::$fn(&self, rhs)}
+        }
+        impl std::ops::$trait<$rhs> for $lhs {
+            type Output = $out;
+            fn $fn(self, rhs: $rhs) -> Self::Output {<&$lhs as std::ops::$trait<&$rhs>>::$fn(&self, &rhs)}
+        }
+    };
 }
 
 /// A *must-implement* trait for all metrics that utilize [defo] macro.
@@ -189,6 +335,8 @@ pub trait DefoAble {
 
 /// A trait for anything that can be sensibly squared (x²).
 pub trait Squared {
+    /// What the square is - e.g. an [Area] for a [SpatialUnit].
+    type Output;
     /// Self squared…
-    fn sq(&self) -> Self;
+    fn sq(&self) -> Self::Output;
 }
\ No newline at end of file
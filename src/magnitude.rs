@@ -0,0 +1,352 @@
+//! Stellar magnitudes
+//! 
+//! Magnitudes live on a (reversed) logarithmic scale - five magnitudes is a flux factor of 100 - and so
+//! [Magnitude] doesn't get the linear [defo!][crate::defo]-operators. Adding two magnitudes combines their fluxes.
+use std::{cmp::Ordering, iter::Sum, ops::Add, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ApproxEq, AsLuminosity, AsSpatialUnit, AstroError, Checked, DefoAble, Luminosity, MetricsInternalType, SpatialUnit, Temperature, Tolerance, format::{FormatMetric, Symbols, display_via_format}, parse::{ParseMetricError, UnitRow, parse_metric_exact}};
+
+/// Zero point of the absolute bolometric magnitude scale, in W (IAU 2015 Resolution B2).
+pub const L0_WATTS: MetricsInternalType = 3.0128e28;
+
+/// Stellar magnitudes.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Magnitude {
+    /// Apparent (visual) magnitude, *m*.
+    Apparent(MetricsInternalType),
+    /// Absolute (visual) magnitude, *M* - as seen from 10 pc.
+    Absolute(MetricsInternalType),
+    /// Apparent bolometric magnitude, *m*<sub>bol</sub>.
+    ApparentBolometric(MetricsInternalType),
+    /// Absolute bolometric magnitude, *M*<sub>bol</sub>.
+    Bolometric(MetricsInternalType),
+}
+
+/// Distance modulus, `μ = m - M = 5 log₁₀(d / 10 pc)`.
+pub fn distance_modulus(distance: SpatialUnit) -> Result<MetricsInternalType, AstroError> {
+    let d = distance.validate()?.pc().raw();
+    if d == 0.0 {
+        return Err(AstroError::OutOfDomain { what: "distance", value: d });
+    }
+    Ok(5.0 * d.log10() - 5.0)
+}
+
+/// Bolometric correction `BC_V` for the given effective temperature.
+/// 
+/// Flower (1996) polynomials with the corrected coefficients of Torres (2010, AJ 140, 1158).
+pub fn bolometric_correction(temperature: Temperature) -> Result<MetricsInternalType, AstroError> {
+    const COOL: [MetricsInternalType; 4] = [
+        -0.190537291496456e5, 0.155144866764412e5, -0.421278819301717e4, 0.381476328422343e3,
+    ];
+    const MID: [MetricsInternalType; 5] = [
+        -0.370510203809015e5, 0.385672629965804e5, -0.150651486316025e5, 0.261724637119416e4, -0.170623810323864e3,
+    ];
+    const HOT: [MetricsInternalType; 6] = [
+        -0.118115450538963e6, 0.137145973583929e6, -0.636233812100225e5, 0.147412923562646e5, -0.170587278406872e4,
+        0.788731721804990e2,
+    ];
+    let t = temperature.checked_k()?.raw();
+    if t <= 0.0 {
+        return Err(AstroError::OutOfDomain { what: "effective temperature", value: t });
+    }
+    let lt = t.log10();
+    let coeffs: &[MetricsInternalType] = match lt {
+        _ if lt < 3.70 => &COOL,
+        _ if lt < 3.90 => &MID,
+        _ => &HOT
+    };
+    // Horner, highest power first.
+    Ok(coeffs.iter().rev().fold(0.0, |acc, c| acc * lt + c))
+}
+
+impl Magnitude {
+    /// The raw magnitude value.
+    pub fn value(&self) -> MetricsInternalType {
+        match self {
+            Self::Apparent(v)           |
+            Self::Absolute(v)           |
+            Self::ApparentBolometric(v) |
+            Self::Bolometric(v) => *v
+        }
+    }
+
+    /// Same kind of magnitude, different value.
+    fn with_value(&self, value: MetricsInternalType) -> Self {
+        match self {
+            Self::Apparent(_) => Self::Apparent(value),
+            Self::Absolute(_) => Self::Absolute(value),
+            Self::ApparentBolometric(_) => Self::ApparentBolometric(value),
+            Self::Bolometric(_) => Self::Bolometric(value),
+        }
+    }
+
+    /// Is `self` an apparent magnitude?
+    pub fn is_apparent(&self) -> bool {
+        matches!(self, Self::Apparent(_) | Self::ApparentBolometric(_))
+    }
+
+    /// Is `self` a bolometric magnitude?
+    pub fn is_bolometric(&self) -> bool {
+        matches!(self, Self::ApparentBolometric(_) | Self::Bolometric(_))
+    }
+
+    /// Is `self` infinitely faint, i.e. no flux at all - of whatever kind?
+    fn is_dark(&self) -> bool {
+        self.value() == MetricsInternalType::INFINITY
+    }
+
+    /// Can `self` and `other` be added up - same kind of magnitude, or either one without any flux?
+    fn is_same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other) || self.is_dark() || other.is_dark()
+    }
+
+    /// Combined magnitude of `self` and `other`, of `self`'s kind (unless `self` is dark).
+    fn combined(&self, other: &Self) -> Self {
+        let flux = MetricsInternalType::powf(10.0, -0.4 * self.value()) + MetricsInternalType::powf(10.0, -0.4 * other.value());
+        let kind = if self.is_dark() { other } else { self };
+        kind.with_value(-2.5 * flux.log10())
+    }
+
+    /// Combined magnitude of all the `sources`, `None` if there are none - the fallible [Sum].
+    pub fn total(sources: impl IntoIterator<Item = Self>) -> Result<Option<Self>, AstroError> {
+        sources.into_iter().try_fold(None, |acc: Option<Self>, m| match acc {
+            None if m.value().is_nan() => Err(AstroError::NaN { metric: "Magnitude" }),
+            None => Ok(Some(m)),
+            Some(a) => a.checked_add(&m).map(Some)
+        })
+    }
+
+    /// `self + rhs`, if both are the same kind of magnitude (or either is infinitely faint) and neither is NaN.
+    pub fn checked_add(&self, rhs: &Self) -> Result<Self, AstroError> {
+        if !self.is_same_kind(rhs) {
+            return Err(AstroError::OutOfDomain { what: "magnitude pair", value: self.value() - rhs.value() });
+        }
+        if self.value().is_nan() || rhs.value().is_nan() {
+            return Err(AstroError::NaN { metric: "Magnitude" });
+        }
+        Ok(self.combined(rhs))
+    }
+
+    /// Flux ratio `F_self / F_other`, i.e. `10^(-0.4 (m_self - m_other))`.
+    pub fn flux_ratio(&self, other: &Self) -> MetricsInternalType {
+        MetricsInternalType::powf(10.0, -0.4 * (self.value() - other.value()))
+    }
+
+    /// `self` as seen from 10 pc, given it's seen at `distance`. Absolute magnitudes are returned as-is.
+    pub fn absolute(&self, distance: SpatialUnit) -> Result<Self, AstroError> {
+        Ok(match self {
+            Self::Apparent(m) => Self::Absolute(m - distance_modulus(distance)?),
+            Self::ApparentBolometric(m) => Self::Bolometric(m - distance_modulus(distance)?),
+            _ => *self
+        })
+    }
+
+    /// `self` as seen from `distance`. Apparent magnitudes are returned as-is.
+    pub fn apparent(&self, distance: SpatialUnit) -> Result<Self, AstroError> {
+        Ok(match self {
+            Self::Absolute(m) => Self::Apparent(m + distance_modulus(distance)?),
+            Self::Bolometric(m) => Self::ApparentBolometric(m + distance_modulus(distance)?),
+            _ => *self
+        })
+    }
+
+    /// Distance from an apparent and absolute magnitude pair (of the same band).
+    pub fn distance(apparent: &Self, absolute: &Self) -> Result<SpatialUnit, AstroError> {
+        if !apparent.is_apparent() || absolute.is_apparent() || apparent.is_bolometric() != absolute.is_bolometric() {
+            return Err(AstroError::OutOfDomain { what: "magnitude pair", value: apparent.value() - absolute.value() });
+        }
+        let mu = apparent.value() - absolute.value();
+        Ok(SpatialUnit::Pc(MetricsInternalType::powf(10.0, (mu + 5.0) / 5.0)))
+    }
+
+    /// Apply the [bolometric_correction] for `temperature` to a visual magnitude.
+    /// Bolometric magnitudes are returned as-is.
+    pub fn bolometric(&self, temperature: Temperature) -> Result<Self, AstroError> {
+        Ok(match self {
+            Self::Apparent(m) => Self::ApparentBolometric(m + bolometric_correction(temperature)?),
+            Self::Absolute(m) => Self::Bolometric(m + bolometric_correction(temperature)?),
+            _ => *self
+        })
+    }
+
+    /// Absolute bolometric magnitude of `luminosity`, `M_bol = -2.5 log₁₀(L / L₀)`.
+    pub fn from_luminosity(luminosity: Luminosity) -> Result<Self, AstroError> {
+        let l = luminosity.validate()?.w().raw();
+        if l == 0.0 {
+            return Err(AstroError::OutOfDomain { what: "luminosity", value: l });
+        }
+        Ok(Self::Bolometric(-2.5 * (l / L0_WATTS).log10()))
+    }
+
+    /// Luminosity of an absolute bolometric magnitude.
+    pub fn luminosity(&self) -> Result<Luminosity, AstroError> {
+        match self {
+            Self::Bolometric(m) => Ok(Luminosity::W(L0_WATTS * MetricsInternalType::powf(10.0, -0.4 * m)).lo()),
+            _ => Err(AstroError::OutOfDomain { what: "non-bolometric/apparent magnitude", value: self.value() })
+        }
+    }
+}
+
+impl Add for Magnitude {
+    type Output = Self;
+    /// Combined magnitude of two sources, `-2.5 log₁₀(10^(-0.4 m₁) + 10^(-0.4 m₂))`.
+    /// 
+    /// # Panics
+    /// Mixing different kinds of magnitudes is meaningless - see [Magnitude::checked_add] for the fallible version.
+    fn add(self, rhs: Self) -> Self::Output {
+        assert!(self.is_same_kind(&rhs), "can't add magnitudes of different kinds: {self:?} + {rhs:?}");
+        self.combined(&rhs)
+    }
+}
+
+impl Sum for Magnitude {
+    /// Combined magnitude of all the sources.
+    /// 
+    /// An empty iterator is infinitely faint - `Apparent(∞)`, which being fluxless adds up with any kind
+    /// of magnitude. See [Magnitude::total] to tell "no sources" apart.
+    /// 
+    /// # Panics
+    /// If the sources aren't all the same kind of magnitude - see [Magnitude::total].
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|a, b| a + b).unwrap_or(Self::Apparent(MetricsInternalType::INFINITY))
+    }
+}
+
+impl PartialEq for Magnitude {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Magnitude {
+    /// Compares the values - mind that smaller is brighter. Different kinds don't compare.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if std::mem::discriminant(self) != std::mem::discriminant(other) {
+            return None;
+        }
+        self.value().partial_cmp(&other.value())
+    }
+}
+
+impl ApproxEq for Magnitude {
+    /// Different kinds are never ≈.
+    fn approx_eq(&self, other: &Self, tolerance: Tolerance) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other) && tolerance.check(self.value(), other.value())
+    }
+}
+
+impl FormatMetric for Magnitude {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.value()) }
+
+    fn format_symbol(&self, _: Symbols) -> &'static str {
+        match self {
+            Self::Apparent(_) => "mag",
+            Self::Absolute(_) => "Mag",
+            Self::ApparentBolometric(_) => "mag(bol)",
+            Self::Bolometric(_) => "Mag(bol)",
+        }
+    }
+
+    fn default_decimals(&self) -> usize { 2 }
+}
+
+display_via_format!(Magnitude);
+
+/// Accepted unit symbols - these are case-sensitive, "mag" vs. "Mag".
+const MAGNITUDE_UNITS: &[UnitRow<Magnitude>] = &[
+    ("mag", Magnitude::Apparent), ("m", Magnitude::Apparent),
+    ("Mag", Magnitude::Absolute), ("M", Magnitude::Absolute),
+    ("mag(bol)", Magnitude::ApparentBolometric), ("m_bol", Magnitude::ApparentBolometric),
+    ("Mag(bol)", Magnitude::Bolometric), ("M_bol", Magnitude::Bolometric),
+];
+
+impl FromStr for Magnitude {
+    type Err = ParseMetricError;
+    /// Parse e.g. "-1.46 mag", "4.83 Mag", "4.74 M_bol".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric_exact(s, MAGNITUDE_UNITS)
+    }
+}
+
+#[cfg(test)]
+mod magnitude_tests {
+    use crate::{AsLuminosity, AsSpatialUnit, AsTemperature, assert_quantity_eq};
+    use super::*;
+
+    #[test]
+    fn flux_combining() {
+        // Two equal stars are 2.5 log₁₀ 2 ≈ 0.753 mag brighter than one.
+        let m = Magnitude::Apparent(5.0) + Magnitude::Apparent(5.0);
+        assert_quantity_eq!(Magnitude::Apparent(5.0 - 2.5 * (2.0 as MetricsInternalType).log10()), m);
+        assert!(Magnitude::Apparent(1.0).flux_ratio(&Magnitude::Apparent(6.0)).approx_eq_default(&100.0));
+        let total: Magnitude = [1.0, 2.0, 3.0].into_iter().map(Magnitude::Apparent).sum();
+        assert!(total < Magnitude::Apparent(1.0));
+    }
+
+    #[test]
+    fn mixed_kinds() {
+        assert_eq!(Ok(Magnitude::Apparent(5.0) + Magnitude::Apparent(5.0)), Magnitude::Apparent(5.0).checked_add(&Magnitude::Apparent(5.0)));
+        assert!(matches!(Magnitude::Apparent(1.0).checked_add(&Magnitude::Absolute(1.0)), Err(AstroError::OutOfDomain { what: "magnitude pair", .. })));
+        assert!(matches!(Magnitude::Bolometric(1.0).checked_add(&Magnitude::Bolometric(MetricsInternalType::NAN)), Err(AstroError::NaN { .. })));
+    }
+
+    #[test]
+    fn totals() {
+        let none = std::iter::empty::<Magnitude>();
+        assert_eq!(Ok(None), Magnitude::total(none.clone()));
+        let dark: Magnitude = none.sum();
+        assert!(matches!(dark, Magnitude::Apparent(v) if v == MetricsInternalType::INFINITY));
+        // Fluxless, so it adds up with any kind.
+        assert_eq!(Magnitude::Absolute(1.0), dark + Magnitude::Absolute(1.0));
+        assert_eq!(Magnitude::Bolometric(1.0), Magnitude::Bolometric(1.0) + dark);
+        let stars = [1.0, 2.0, 3.0].map(Magnitude::Absolute);
+        assert_eq!(Ok(Some(stars.into_iter().sum())), Magnitude::total(stars));
+        assert!(matches!(Magnitude::total([Magnitude::Apparent(1.0), Magnitude::Absolute(2.0)]), Err(AstroError::OutOfDomain { .. })));
+        assert!(matches!(Magnitude::total([Magnitude::Apparent(MetricsInternalType::NAN)]), Err(AstroError::NaN { .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "different kinds")]
+    fn mixed_kinds_sum_panics() {
+        let _: Magnitude = [Magnitude::Apparent(1.0), Magnitude::Absolute(2.0)].into_iter().sum();
+    }
+
+    #[test]
+    fn distance_modulus_works() {
+        assert_eq!(Ok(0.0), distance_modulus(10.pc()));
+        // Sirius: V = -1.46 at 2.64 pc → M_V ≈ 1.43.
+        let abs = Magnitude::Apparent(-1.46).absolute(2.64.pc()).unwrap();
+        assert!(abs.approx_eq(&Magnitude::Absolute(1.43), Tolerance::absolute(0.01)));
+        assert_quantity_eq!(2.64.pc(), Magnitude::distance(&Magnitude::Apparent(-1.46), &abs).unwrap(), Tolerance::relative(1e-12));
+        assert!(Magnitude::distance(&abs, &abs).is_err());
+        assert!(distance_modulus(0.pc()).is_err());
+    }
+
+    #[test]
+    fn bolometric() {
+        assert!(Magnitude::from_luminosity(1.lo()).unwrap().approx_eq(&Magnitude::Bolometric(4.74), Tolerance::absolute(0.001)));
+        assert_quantity_eq!(1.lo(), Magnitude::Bolometric(4.74).luminosity().unwrap(), Tolerance::relative(1e-3));
+        // The Sun: BC_V ≈ -0.07, so M_V 4.81 → M_bol ≈ 4.74.
+        let bc = bolometric_correction(5772.k()).unwrap();
+        assert!((bc + 0.07).abs() < 0.02, "{bc}");
+        assert!(Magnitude::Absolute(4.81).bolometric(5772.k()).unwrap().approx_eq(&Magnitude::Bolometric(4.74), Tolerance::absolute(0.02)));
+        assert!(bolometric_correction(Temperature::X).is_err());
+    }
+
+    #[test]
+    fn parsing_and_display() {
+        assert_eq!(Ok(Magnitude::Absolute(4.83)), "4.83 Mag".parse());
+        assert_eq!(Ok(Magnitude::Apparent(-1.46)), "-1.46 mag".parse());
+        assert_eq!("4.74 Mag(bol)", Magnitude::Bolometric(4.74).to_string());
+    }
+
+    #[test]
+    fn parsing_is_case_sensitive() {
+        assert!("4.83 MAG".parse::<Magnitude>().is_err());
+        assert!("4.83 M_BOL".parse::<Magnitude>().is_err());
+        assert_eq!(Ok(Magnitude::Bolometric(4.74)), "4.74 M_bol".parse());
+        assert_eq!(Ok(Magnitude::ApparentBolometric(4.74)), "4.74 m_bol".parse());
+    }
+}
@@ -1,11 +1,11 @@
 //! Mass
 //! 
 //! Grams, kilograms, M⊕, M♃, and M☉
-use std::{cmp::Ordering, fmt::Display, ops::{Add, Div, Mul, Sub}};
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{AsMass, DefoAble, MetricsInternalType, defo, ratio};
+use crate::{AsMass, DefoAble, MetricsInternalType, UnitTable, defo, units::UnitFactor, parse::{ParseMetricError, UnitRow, parse_metric}, format::{FormatMetric, Symbols, display_via_format}};
 use paste::paste;
 
 /// Some mass "magnitudes".
@@ -45,114 +45,32 @@ impl DefoAble for Mass {
     }
 
     fn cnv_into(&self, other: &Self) -> Self {
-        match other {
-            Self::G(_) => self.g(),
-            Self::Kg(_) => self.kg(),
-            Self::ME(_) => self.me(),
-            Self::MJ(_) => self.mj(),
-            Self::MO(_) => self.mo()
-        }
+        self.to_unit(other)
     }
 }
 
 impl Mass {
-    /// Elevates the lower-magnitude mass of the two into higher-magnitude one.
-    fn unify(&self, other: &Self) -> (Self, Self) {
-        let rank = |m:&Mass| match m {
-            Self::MO(_) => 5,
-            Self::MJ(_) => 4,
-            Self::ME(_) => 3,
-            Self::Kg(_) => 2,
-            Self::G(_) => 1
-        };
-
-        match rank(self).cmp(&rank(other)) {
-            Ordering::Greater => {
-                let other_c = match self {
-                    Self::MO(_) => other.mo(),
-                    Self::MJ(_) => other.mj(),
-                    Self::ME(_) => other.me(),
-                    Self::Kg(_) => other.kg(),
-                    Self::G(_) => other.g()
-                };
-                (*self, other_c)
-            },
-            Ordering::Less => {
-                let self_c = self.cnv_into(other);
-                (self_c, *other)
-            },
-            Ordering::Equal => (*self, *other)
-        }
-    }
-
     /// self → `f64`
     pub fn as_f64(&self) -> f64 { self.into() }
 }
 
-const SOL_KG: MetricsInternalType = 1.98847e30;
-const JUP_KG: MetricsInternalType = 1.89813e27;
-const EARTH_KG: MetricsInternalType = 5.9722e24;
-
-/// Grams to kg.
-const fn g_to_kg(g: MetricsInternalType) -> MetricsInternalType {
-    g / 1_000.0 as MetricsInternalType
-}
-
-/// kg to grams.
-const fn kg_to_g(kg: MetricsInternalType) -> MetricsInternalType {
-    kg * 1_000.0 as MetricsInternalType
+impl UnitTable for Mass {
+    const SI_UNIT: &'static str = "kg";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::G(0.0), |_| 1e-3),
+        (Self::Kg(0.0), |_| 1.0),
+        (Self::ME(0.0), |cs| cs.m_earth.value),
+        (Self::MJ(0.0), |cs| cs.m_jup.value),
+        (Self::MO(0.0), |cs| cs.m_sun.value),
+    ];
 }
 
 impl AsMass for Mass {
-    fn mo(&self) -> Mass {
-        match self {
-            Self::MO(_) => *self,
-            Self::MJ(v) => Self::MO(*v * ratio(JUP_KG, SOL_KG)),
-            Self::ME(v) => Self::MO(*v * ratio(EARTH_KG, SOL_KG)),
-            Self::Kg(v) => Self::MO(*v / SOL_KG),
-            Self::G(v) => Self::MO(*v * kg_to_g(SOL_KG)),
-        }
-    }
-
-    fn mj(&self) -> Mass {
-        match self {
-            Self::MO(v) => Self::MJ(*v * ratio(SOL_KG, JUP_KG)),
-            Self::MJ(_) => *self,
-            Self::ME(v) => Self::MJ(*v * ratio(EARTH_KG, JUP_KG)),
-            Self::Kg(v) => Self::MJ(*v * JUP_KG),
-            Self::G(v) => Self::MJ(*v / kg_to_g(JUP_KG)),
-        }
-    }
-
-    fn me(&self) -> Mass {
-        match self {
-            Self::MO(v) => Self::ME(*v * ratio(SOL_KG, EARTH_KG)),
-            Self::MJ(v) => Self::ME(*v * ratio(JUP_KG, EARTH_KG)),
-            Self::ME(_) => *self,
-            Self::Kg(v) => Self::ME(*v / EARTH_KG),
-            Self::G(v) => Self::ME(*v / kg_to_g(EARTH_KG)),
-        }
-    }
-
-    fn kg(&self) -> Mass {
-        match self {
-            Self::MO(v) => Self::Kg(*v * SOL_KG),
-            Self::MJ(v) => Self::Kg(*v * JUP_KG),
-            Self::ME(v) => Self::Kg(*v * EARTH_KG),
-            Self::Kg(_) => *self,
-            Self::G(v) => Self::Kg(g_to_kg(*v))
-        }
-    }
-
-    fn g(&self) -> Mass {
-        match self {
-            Self::MO(v) => Self::G(*v * kg_to_g(SOL_KG)),
-            Self::MJ(v) => Self::G(*v * kg_to_g(JUP_KG)),
-            Self::ME(v) => Self::G(*v * kg_to_g(EARTH_KG)),
-            Self::Kg(v) => Self::G(kg_to_g(*v)),
-            Self::G(_) => *self
-        }
-    }
+    fn mo(&self) -> Mass { self.to_unit(&Self::MO(0.0)) }
+    fn mj(&self) -> Mass { self.to_unit(&Self::MJ(0.0)) }
+    fn me(&self) -> Mass { self.to_unit(&Self::ME(0.0)) }
+    fn kg(&self) -> Mass { self.to_unit(&Self::Kg(0.0)) }
+    fn g(&self) -> Mass { self.to_unit(&Self::G(0.0)) }
 }
 
 /// Macro to define [AsMass] impls for a variety of primitives.
@@ -194,15 +112,14 @@ impl PartialEq for Mass {
 
 impl PartialOrd for Mass {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let (s, o) = self.unify(other);
-        Some(s.raw().total_cmp(&o.raw()))
+        Some(self.cmp(other))
     }
 }
 
 impl Eq for Mass {}
 impl Ord for Mass {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.cmp_in_larger_unit(other)
     }
 }
 
@@ -213,16 +130,54 @@ define_asmass_for_prim!(f [32, 64, 128]);
 define_asmass_for_prim!(8, 16, 32, 64, 128, size);
 defo!(Mass; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
 
-impl Display for Mass {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl FormatMetric for Mass {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::MO(_), Symbols::Unicode) => "M☉",
+            (Self::MO(_), Symbols::Ascii) => "Msun",
+            (Self::MJ(_), Symbols::Unicode) => "M♃",
+            (Self::MJ(_), Symbols::Ascii) => "Mjup",
+            (Self::ME(_), Symbols::Unicode) => "M⊕",
+            (Self::ME(_), Symbols::Ascii) => "Mearth",
+            (Self::Kg(_), _) => "kg",
+            (Self::G(_), _) => "g",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
         match self {
-            Self::MO(v) => write!(f, "{:.2} M☉", v),
-            Self::MJ(v) => write!(f, "{:.3} M♃", v),
-            Self::ME(v) => write!(f, "{:.3} M⊕", v),
-            Self::Kg(v) => write!(f, "{:.1} kg", v),// preferably use grams if you need more than one decimal…
-            Self::G(v) => write!(f, "{:.0}g", v),// there's no mg (yet), but less than gram is not really in the menu for *this* library, currently.
+            Self::MO(_) => 2,
+            Self::MJ(_) |
+            Self::ME(_) => 3,
+            Self::Kg(_) => 1,// preferably use grams if you need more than one decimal…
+            Self::G(_) => 0,// there's no mg (yet), but less than gram is not really in the menu for *this* library, currently.
         }
     }
+
+    fn default_spaced(&self) -> bool {
+        !matches!(self, Self::G(_))
+    }
+}
+
+display_via_format!(Mass);
+
+/// Accepted unit symbols - what [Display] writes plus the usual ASCII aliases.
+const MASS_UNITS: &[UnitRow<Mass>] = &[
+    ("g", Mass::G),
+    ("kg", Mass::Kg),
+    ("M⊕", Mass::ME), ("ME", Mass::ME), ("Mearth", Mass::ME), ("M_earth", Mass::ME),
+    ("M♃", Mass::MJ), ("MJ", Mass::MJ), ("Mjup", Mass::MJ), ("M_jup", Mass::MJ),
+    ("M☉", Mass::MO), ("M⊙", Mass::MO), ("MO", Mass::MO), ("Msun", Mass::MO), ("M_sun", Mass::MO), ("Msol", Mass::MO),
+];
+
+impl FromStr for Mass {
+    type Err = ParseMetricError;
+    /// Parse e.g. "1.5 M☉", "1.5Msun", "5.97e24 kg".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, MASS_UNITS)
+    }
 }
 
 #[cfg(test)]
@@ -241,6 +196,7 @@ mod mass_tests {
     }
 
     #[test]
+    #[allow(clippy::op_ref)]// borrowed ops are what we're testing here…
     fn operators() {
         let a = 1.kg();
         let b = 0.5.kg();
@@ -248,4 +204,25 @@ mod mass_tests {
         assert_eq!(1.5.kg(), a_b);
         assert!(1.5.kg() == a_b);// see that Ord impl works
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Ok(Mass::MO(1.5)), "1.5 M☉".parse());
+        assert_eq!(Ok(Mass::MJ(2e-3)), "2e-3 mjup".parse());
+        assert_eq!(Ok(Mass::ME(12.0)), "12M⊕".parse());
+        assert_eq!(Ok(Mass::G(1.0)), 1.g().to_string().parse());
+        let e = "1.5 Mpluto".parse::<Mass>().unwrap_err();
+        assert_eq!(4, e.position());
+    }
+
+    #[test]
+    fn formatting() {
+        use crate::format::{FormatOptions, Spacing};
+        assert_eq!("1.00 M☉", 1.mo().to_string());
+        assert_eq!("1.000 M☉", format!("{:.3}", 1.mo()));
+        assert_eq!("1.988e30 kg", format!("{:.3e}", 1.mo().kg()));
+        assert_eq!("  1.00 M☉", format!("{:>9}", 1.mo()));
+        assert_eq!("1.0 Mjup", format!("{:#.1}", 1.mj()));
+        assert_eq!("1.99e30kg", 1.mo().kg().format_with(FormatOptions::default().significant(3).scientific().spacing(Spacing::Tight)));
+    }
+}
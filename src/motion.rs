@@ -0,0 +1,190 @@
+//! Proper motion and space motion
+//! 
+//! Rigorous propagation of catalogue astrometry between epochs: position, parallax, proper motion and radial
+//! velocity are turned into a straight-line space motion (which takes care of perspective acceleration), moved
+//! along, and turned back. Light-time within the system is ignored, as in most catalogue work.
+use serde::{Deserialize, Serialize};
+
+use crate::{Angle, AsAngle, AsSpatialUnit, AsTime, AsVelocity, AstroError, Checked, DefoAble, Epoch, Frame, MetricsInternalType, SkyCoord, SpatialUnit, Velocity, coords::{dot, mul_vec, spherical, unit_vector}};
+
+/// Smallest parallax used for propagation; anything below (incl. negative ones) is taken as this, which puts
+/// the star ~10 Mpc away with no radial motion to speak of.
+const MIN_PARALLAX_MAS: MetricsInternalType = 1e-4;
+
+/// Proper motion, per Julian year.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct ProperMotion {
+    /// μα* = μα cos δ.
+    pub ra_cosdec: Angle,
+    /// μδ.
+    pub dec: Angle,
+}
+
+impl ProperMotion {
+    /// μα*, μδ per Julian year.
+    pub fn new(ra_cosdec: Angle, dec: Angle) -> Self {
+        Self { ra_cosdec, dec }
+    }
+
+    /// Total proper motion per Julian year.
+    pub fn total(&self) -> Angle {
+        Angle::Mas(self.ra_cosdec.mas().raw().hypot(self.dec.mas().raw()))
+    }
+}
+
+/// Heliocentric galactic space velocity: U towards the Galactic centre, V in the direction of rotation, W towards
+/// the north Galactic pole. Not corrected for solar motion (LSR).
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Uvw {
+    /// Towards the Galactic centre.
+    pub u: Velocity,
+    /// Towards Galactic rotation.
+    pub v: Velocity,
+    /// Towards the NGP.
+    pub w: Velocity,
+}
+
+/// Catalogue astrometry of a star: ICRS position, parallax, proper motion and radial velocity, at an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Astrometry {
+    /// Right ascension.
+    pub ra: Angle,
+    /// Declination.
+    pub dec: Angle,
+    /// Parallax.
+    pub parallax: Angle,
+    /// Proper motion.
+    pub proper_motion: ProperMotion,
+    /// Radial velocity, positive receding.
+    pub radial_velocity: Velocity,
+    /// Epoch of the position (TDB, or TT, ideally).
+    pub epoch: Epoch,
+}
+
+/// Local unit vectors towards increasing α and δ at `ra`, `dec`.
+fn local_basis(ra: Angle, dec: Angle) -> ([MetricsInternalType; 3], [MetricsInternalType; 3]) {
+    let (sa, ca, sd, cd) = (ra.sin(), ra.cos(), dec.sin(), dec.cos());
+    ([-sa, ca, 0.0], [-sd * ca, -sd * sa, cd])
+}
+
+impl Astrometry {
+    /// Astrometry at `epoch`.
+    pub fn new(ra: Angle, dec: Angle, parallax: Angle, proper_motion: ProperMotion, radial_velocity: Velocity, epoch: Epoch) -> Self {
+        Self { ra, dec, parallax, proper_motion, radial_velocity, epoch }
+    }
+
+    /// Distance by parallax inversion, as [SpatialUnit::Pc]. See [crate::Parallax] for better estimates.
+    pub fn distance(&self) -> Result<SpatialUnit, AstroError> {
+        match self.parallax.mas().raw() {
+            p if p > 0.0 => Ok(self.parallax.distance_for(1.au()).pc()),
+            p => Err(AstroError::OutOfDomain { what: "parallax", value: p })
+        }
+    }
+
+    /// The position as an ICRS [SkyCoord], with distance if the parallax allows.
+    pub fn sky_coord(&self) -> Result<SkyCoord, AstroError> {
+        let c = SkyCoord::icrs(self.ra, self.dec)?;
+        Ok(match self.distance() {
+            Ok(d) => c.with_distance(d),
+            Err(_) => c
+        })
+    }
+
+    /// Transverse velocity, `4.74 km/s × μ["/yr] / ϖ["]`.
+    pub fn tangential_velocity(&self) -> Result<Velocity, AstroError> {
+        let d = self.distance()?;
+        Ok(Velocity::AuYr(self.proper_motion.total().size_at(d).au().raw()).km_s())
+    }
+
+    /// Position (au) and velocity (au/yr) in ICRS Cartesian coordinates.
+    pub(crate) fn state(&self) -> Result<([MetricsInternalType; 3], [MetricsInternalType; 3]), AstroError> {
+        let p_mas = self.parallax.validate()?.mas().raw().max(MIN_PARALLAX_MAS);
+        let r = Angle::Mas(p_mas).distance_for(1.au()).raw();
+        let u = unit_vector(self.ra, self.dec);
+        let (ea, ed) = local_basis(self.ra, self.dec);
+        let (ma, md) = (self.proper_motion.ra_cosdec.validate()?.rad().raw(), self.proper_motion.dec.validate()?.rad().raw());
+        let vr = self.radial_velocity.validate()?.au_yr().raw();
+        let pos = u.map(|c| c * r);
+        let vel = [0, 1, 2].map(|i| r * (ma * ea[i] + md * ed[i]) + vr * u[i]);
+        Ok((pos, vel))
+    }
+
+    /// Space velocity in ICRS Cartesian coordinates.
+    pub fn space_velocity(&self) -> Result<[Velocity; 3], AstroError> {
+        Ok(self.state()?.1.map(|v| Velocity::AuYr(v).km_s()))
+    }
+
+    /// Galactic UVW velocity.
+    pub fn uvw(&self) -> Result<Uvw, AstroError> {
+        let [u, v, w] = mul_vec(&Frame::Galactic.rotation_from_icrs(), self.state()?.1).map(|c| Velocity::AuYr(c).km_s());
+        Ok(Uvw { u, v, w })
+    }
+
+    /// `self` propagated (rigorously, with perspective acceleration) to `epoch`.
+    pub fn propagate(&self, epoch: Epoch) -> Result<Astrometry, AstroError> {
+        let (pos, vel) = self.state()?;
+        let dt = (epoch - self.epoch).yr().raw();
+        let p = [0, 1, 2].map(|i| pos[i] + vel[i] * dt);
+        let r = dot(&p, &p).sqrt();
+        let (ra, dec) = spherical(p);
+        let u = p.map(|c| c / r);
+        let (ea, ed) = local_basis(ra, dec);
+        let pm = ProperMotion::new(Angle::Rad(dot(&vel, &ea) / r).mas(), Angle::Rad(dot(&vel, &ed) / r).mas());
+        // A sub-minimum parallax stays as it was - there's no information in it to propagate.
+        let parallax = match self.parallax.mas().raw() {
+            p if p >= MIN_PARALLAX_MAS => Angle::subtended(1.au(), SpatialUnit::Au(r)).mas(),
+            _ => self.parallax
+        };
+        Ok(Astrometry {
+            ra, dec, parallax,
+            proper_motion: pm,
+            radial_velocity: Velocity::AuYr(dot(&vel, &u)).km_s(),
+            epoch,
+        })
+    }
+}
+
+#[cfg(test)]
+mod motion_tests {
+    use crate::{ApproxEq, AsSpatialUnit, Tolerance, TimeScale, assert_quantity_eq};
+    use super::*;
+
+    fn barnard() -> Astrometry {
+        // Gaia DR3-ish, at J2016.0.
+        Astrometry::new(269.448_502_3.deg(), 4.739_420_9.deg(), 546.98.mas(),
+            ProperMotion::new((-801.55).mas(), 10_362.39.mas()), (-110.6).km_s(), Epoch::from_julian_epoch(2016.0, TimeScale::Tdb))
+    }
+
+    #[test]
+    fn perspective_acceleration() {
+        let b = barnard();
+        let later = b.propagate(Epoch::from_julian_epoch(2116.0, TimeScale::Tdb)).unwrap();
+        // dμ/dt = -2 μ v_r / r ≈ +1.28 mas/yr², so after a century μ is ~128 mas/yr bigger.
+        let dmu = later.proper_motion.total().mas().raw() - b.proper_motion.total().mas().raw();
+        assert!((dmu - 128.0).abs() < 3.0, "{dmu}");
+        assert!(later.parallax > b.parallax);
+        // And back again.
+        let back = later.propagate(b.epoch).unwrap();
+        assert!(back.ra.approx_eq(&b.ra, Tolerance::absolute(1e-12)));
+        assert!(back.dec.approx_eq(&b.dec, Tolerance::absolute(1e-12)));
+        assert!(back.parallax.approx_eq(&b.parallax, Tolerance::relative(1e-10)));
+        assert!(back.radial_velocity.approx_eq(&b.radial_velocity, Tolerance::relative(1e-10)));
+    }
+
+    #[test]
+    fn velocities() {
+        let b = barnard();
+        let vt = b.tangential_velocity().unwrap();
+        assert!(vt.approx_eq(&(4.740_470_463_5 * 10.393_346 / 0.546_98).km_s(), Tolerance::relative(1e-5)));
+        let [x, y, z] = b.space_velocity().unwrap().map(|v| v.km_s().raw());
+        let speed = (x * x + y * y + z * z).sqrt();
+        assert!(speed.approx_eq(&vt.km_s().raw().hypot(110.6), Tolerance::relative(1e-12)));
+        // Receding straight from the Galactic centre's direction: all U.
+        let gc = SkyCoord::galactic(0.deg(), 0.deg()).unwrap().to_frame(Frame::Icrs);
+        let s = Astrometry::new(gc.lon(), gc.lat(), 10.mas(), ProperMotion::new(0.mas(), 0.mas()), 10.km_s(), Epoch::j2000());
+        let uvw = s.uvw().unwrap();
+        assert!(uvw.u.approx_eq(&10.km_s(), Tolerance::relative(1e-9)));
+        assert!(uvw.v.km_s().raw().abs() < 1e-8 && uvw.w.km_s().raw().abs() < 1e-8);
+        assert_quantity_eq!(100.pc(), s.distance().unwrap(), Tolerance::relative(1e-12));
+    }
+}
@@ -0,0 +1,382 @@
+//! Kepler orbits
+//! 
+//! Two-body orbits of any conic section - ellipse, parabola or hyperbola - described by their classical
+//! elements, with Kepler's equation solved for position along the orbit and conversion to/from Cartesian
+//! state vectors. The reference plane is whatever the state vectors' x-y plane is (e.g. the ecliptic).
+use serde::{Deserialize, Serialize};
+
+mod limits;
+pub use limits::{RocheModel, hill_radius, roche_limit, roche_limit_from_masses, sphere_of_influence};
+
+use crate::{Angle, AsAngle, AsSpatialUnit, AsTime, AsVelocity, AstroError, Checked, DefoAble, Mass, MetricsInternalType, PI, SpatialUnit, SpatialVector, TAU, Time, UnitTable, Velocity, coords::dot, derived::gm};
+
+type Vec3 = [MetricsInternalType; 3];
+
+/// Eccentricities closer than this to 1 are taken as parabolic, and closer to 0 as circular.
+const ECC_EPSILON: MetricsInternalType = 1e-11;
+/// Newton iterations before Kepler's equation is deemed unsolvable.
+const MAX_ITERATIONS: usize = 64;
+
+/// Gravitational parameter of `central` + `body`, in m³/s².
+fn mu(central: Mass, body: Mass) -> Result<MetricsInternalType, AstroError> {
+    match gm(central)? + gm(body)? {
+        mu if mu > 0.0 => Ok(mu),
+        mu => Err(AstroError::OutOfDomain { what: "total mass", value: mu })
+    }
+}
+
+/// Kepler's third law - orbital period of two bodies of `total_mass` on an orbit of `semi_major_axis`.
+pub fn kepler_period(total_mass: Mass, semi_major_axis: SpatialUnit) -> Result<Time, AstroError> {
+    let a = semi_major_axis.validate()?.m().raw();
+    if a <= 0.0 {
+        return Err(AstroError::OutOfDomain { what: "semi-major axis", value: a });
+    }
+    Ok(Time::S(TAU * (a.powi(3) / mu(total_mass, Mass::Kg(0.0))?).sqrt()).yr())
+}
+
+/// True anomaly for the given mean anomaly and eccentricity.
+/// 
+/// "Mean anomaly" is the usual `E - e sin E` for ellipses, `e sinh H - H` for hyperbolae, and Barker's
+/// `D + D³/3` (with `D = tan ν/2`) for parabolae - i.e. whatever grows linearly with time.
+pub fn solve_kepler(mean_anomaly: Angle, eccentricity: MetricsInternalType) -> Result<Angle, AstroError> {
+    let m = mean_anomaly.validate()?.rad().raw();
+    let e = eccentricity;
+    if !e.is_finite() || e < 0.0 {
+        return Err(AstroError::OutOfDomain { what: "eccentricity", value: e });
+    }
+    let nu = if (e - 1.0).abs() < ECC_EPSILON {
+        // Barker's cubic, solved on the positive side to dodge cancellation.
+        let w = (1.5 * m.abs() + (1.0 + 2.25 * m * m).sqrt()).cbrt();
+        2.0 * (w - 1.0 / w).copysign(m).atan()
+    } else if e < 1.0 {
+        let m = (m + PI).rem_euclid(TAU) - PI;
+        let mut ea = if e > 0.8 { PI.copysign(m) } else { m + e * m.sin() };
+        newton(&mut ea, |x| (x - e * x.sin() - m, 1.0 - e * x.cos()))?;
+        2.0 * ((1.0 + e).sqrt() * (ea / 2.0).sin()).atan2((1.0 - e).sqrt() * (ea / 2.0).cos())
+    } else {
+        let mut h = (m / e).asinh();
+        newton(&mut h, |x| (e * x.sinh() - x - m, e * x.cosh() - 1.0))?;
+        2.0 * (((e + 1.0) / (e - 1.0)).sqrt() * (h / 2.0).tanh()).atan()
+    };
+    Ok(Angle::Rad(nu).deg().normalized())
+}
+
+fn newton(x: &mut MetricsInternalType, f: impl Fn(MetricsInternalType) -> (MetricsInternalType, MetricsInternalType)) -> Result<(), AstroError> {
+    for _ in 0..MAX_ITERATIONS {
+        let (y, dy) = f(*x);
+        let step = y / dy;
+        *x -= step;
+        if step.abs() <= 1e-15 * x.abs().max(1.0) {
+            return Ok(());
+        }
+    }
+    Err(AstroError::OutOfDomain { what: "Kepler's equation (no convergence)", value: *x })
+}
+
+/// A two-body Keplerian orbit.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct Orbit {
+    central: Mass,
+    body: Mass,
+    /// Periapsis distance - unlike the semi-major axis, finite for every conic.
+    periapsis: SpatialUnit,
+    eccentricity: MetricsInternalType,
+    inclination: Angle,
+    node: Angle,
+    arg_periapsis: Angle,
+    true_anomaly: Angle,
+}
+
+impl Orbit {
+    /// An orbit of `body` around `central`, in the reference plane, at periapsis.
+    /// 
+    /// For hyperbolic orbits (`eccentricity` > 1) `semi_major_axis` is taken as its magnitude, |a|;
+    /// for parabolic ones see [Orbit::parabolic].
+    pub fn new(central: Mass, body: Mass, semi_major_axis: SpatialUnit, eccentricity: MetricsInternalType) -> Result<Self, AstroError> {
+        mu(central, body)?;
+        let a = semi_major_axis.validate()?.raw();
+        let e = eccentricity;
+        if !e.is_finite() || e < 0.0 || (e - 1.0).abs() < ECC_EPSILON {
+            return Err(AstroError::OutOfDomain { what: "eccentricity", value: e });
+        }
+        if a == 0.0 {
+            return Err(AstroError::OutOfDomain { what: "semi-major axis", value: a });
+        }
+        let mut periapsis = semi_major_axis;
+        periapsis.set(a * (1.0 - e).abs());
+        Ok(Self::from_parts(central, body, periapsis, e))
+    }
+
+    /// A parabolic orbit with the given `periapsis` distance.
+    pub fn parabolic(central: Mass, body: Mass, periapsis: SpatialUnit) -> Result<Self, AstroError> {
+        mu(central, body)?;
+        match periapsis.validate()?.raw() {
+            q if q > 0.0 => Ok(Self::from_parts(central, body, periapsis, 1.0)),
+            q => Err(AstroError::OutOfDomain { what: "periapsis", value: q })
+        }
+    }
+
+    fn from_parts(central: Mass, body: Mass, periapsis: SpatialUnit, eccentricity: MetricsInternalType) -> Self {
+        Self {
+            central, body, periapsis, eccentricity,
+            inclination: 0.deg(), node: 0.deg(), arg_periapsis: 0.deg(), true_anomaly: 0.deg(),
+        }
+    }
+
+    /// `self` tilted by `inclination`, with the ascending `node` and argument of periapsis as given.
+    pub fn with_orientation(mut self, inclination: Angle, node: Angle, arg_periapsis: Angle) -> Self {
+        self.inclination = inclination;
+        self.node = node.normalized();
+        self.arg_periapsis = arg_periapsis.normalized();
+        self
+    }
+
+    /// `self` with the body at `true_anomaly`.
+    /// 
+    /// Hyperbolae only reach as far round as their asymptotes, so there |ν| must stay below `acos(-1/e)`.
+    pub fn with_true_anomaly(mut self, true_anomaly: Angle) -> Result<Self, AstroError> {
+        let nu = true_anomaly.validate()?.rad().normalized_signed().raw();
+        let e = self.eccentricity;
+        if e > 1.0 && (e - 1.0).abs() >= ECC_EPSILON && nu.abs() >= (-1.0 / e).acos() {
+            return Err(AstroError::OutOfDomain { what: "true anomaly", value: true_anomaly.deg().raw() });
+        }
+        self.true_anomaly = true_anomaly.normalized();
+        Ok(self)
+    }
+
+    /// `self` with the body at `mean_anomaly` - see [solve_kepler] for what that means off ellipses.
+    pub fn with_mean_anomaly(self, mean_anomaly: Angle) -> Result<Self, AstroError> {
+        self.with_true_anomaly(solve_kepler(mean_anomaly, self.eccentricity)?)
+    }
+
+    /// Mass of the central body.
+    pub fn central(&self) -> Mass { self.central }
+    /// Mass of the orbiting body.
+    pub fn body(&self) -> Mass { self.body }
+    /// Eccentricity, e - 0 for a circle, 1 for a parabola.
+    pub fn eccentricity(&self) -> MetricsInternalType { self.eccentricity }
+    /// Periapsis distance, q.
+    pub fn periapsis(&self) -> SpatialUnit { self.periapsis }
+    /// Inclination to the reference plane, i.
+    pub fn inclination(&self) -> Angle { self.inclination }
+    /// Longitude of the ascending node, Ω.
+    pub fn node(&self) -> Angle { self.node }
+    /// Argument of periapsis, ω.
+    pub fn arg_periapsis(&self) -> Angle { self.arg_periapsis }
+    /// True anomaly, ν.
+    pub fn true_anomaly(&self) -> Angle { self.true_anomaly }
+
+    /// Is the orbit closed, i.e. an ellipse?
+    pub fn is_bound(&self) -> bool { self.eccentricity < 1.0 }
+
+    /// Semi-major axis - |a| for hyperbolae, `None` for a parabola.
+    pub fn semi_major_axis(&self) -> Option<SpatialUnit> {
+        match self.eccentricity {
+            e if (e - 1.0).abs() < ECC_EPSILON => None,
+            e => Some(self.periapsis / (1.0 - e).abs())
+        }
+    }
+
+    /// Apoapsis distance, for ellipses.
+    pub fn apoapsis(&self) -> Option<SpatialUnit> {
+        let e = self.eccentricity;
+        self.is_bound().then(|| self.periapsis * ((1.0 + e) / (1.0 - e)))
+    }
+
+    /// Orbital period, for ellipses.
+    pub fn period(&self) -> Option<Time> {
+        match self.is_bound() {
+            true => kepler_period(self.central + self.body, self.semi_major_axis()?).ok(),
+            false => None
+        }
+    }
+
+    /// Semi-latus rectum, m.
+    fn semi_latus(&self) -> MetricsInternalType {
+        self.periapsis.m().raw() * (1.0 + self.eccentricity)
+    }
+
+    fn mu(&self) -> MetricsInternalType {
+        // Validated at construction - only a hand-edited, deserialized orbit can end up with NaN here.
+        mu(self.central, self.body).unwrap_or(MetricsInternalType::NAN)
+    }
+
+    /// Rate of the mean anomaly, rad/s.
+    fn mean_motion(&self) -> MetricsInternalType {
+        let q = self.periapsis.m().raw();
+        match self.semi_major_axis() {
+            Some(a) => (self.mu() / a.m().raw().powi(3)).sqrt(),
+            None => (self.mu() / (2.0 * q.powi(3))).sqrt()
+        }
+    }
+
+    /// Mean anomaly - see [solve_kepler] for what that means off ellipses.
+    pub fn mean_anomaly(&self) -> Angle {
+        let e = self.eccentricity;
+        let nu = self.true_anomaly.rad().raw();
+        let m = if (e - 1.0).abs() < ECC_EPSILON {
+            let d = (nu / 2.0).tan();
+            d + d.powi(3) / 3.0
+        } else if e < 1.0 {
+            let ea = 2.0 * ((1.0 - e).sqrt() * (nu / 2.0).sin()).atan2((1.0 + e).sqrt() * (nu / 2.0).cos());
+            (ea - e * ea.sin()).rem_euclid(TAU)
+        } else {
+            let nu = (nu + PI).rem_euclid(TAU) - PI;
+            let h = 2.0 * (((e - 1.0) / (e + 1.0)).sqrt() * (nu / 2.0).tan()).atanh();
+            e * h.sinh() - h
+        };
+        Angle::Rad(m).deg()
+    }
+
+    /// `self` after `dt` (which may be negative) has passed.
+    pub fn propagate(&self, dt: Time) -> Result<Self, AstroError> {
+        let m = self.mean_anomaly().rad().raw() + self.mean_motion() * dt.validate()?.s().raw();
+        self.with_mean_anomaly(Angle::Rad(m))
+    }
+
+    /// Unit vectors towards periapsis (P) and 90° ahead of it in the orbital plane (Q).
+    fn perifocal_basis(&self) -> (Vec3, Vec3) {
+        let (so, co) = (self.node.sin(), self.node.cos());
+        let (sw, cw) = (self.arg_periapsis.sin(), self.arg_periapsis.cos());
+        let (si, ci) = (self.inclination.sin(), self.inclination.cos());
+        (
+            [co * cw - so * sw * ci, so * cw + co * sw * ci, sw * si],
+            [-co * sw - so * cw * ci, -so * sw + co * cw * ci, cw * si],
+        )
+    }
+
+    /// Position (in the unit of [Orbit::periapsis]) and velocity of the body relative to the central mass.
+    pub fn state_vectors(&self) -> (SpatialVector, [Velocity; 3]) {
+        let (p, e) = (self.semi_latus(), self.eccentricity);
+        let (sn, cn) = (self.true_anomaly.sin(), self.true_anomaly.cos());
+        let r = p / (1.0 + e * cn);
+        let v = (self.mu() / p).sqrt();
+        let (bp, bq) = self.perifocal_basis();
+        let pos = [0, 1, 2].map(|i| r * (cn * bp[i] + sn * bq[i]));
+        let vel = [0, 1, 2].map(|i| v * (-sn * bp[i] + (e + cn) * bq[i]));
+        (SpatialVector::from_raw(pos, SpatialUnit::M(0.0)).to_unit(&self.periapsis), vel.map(|c| Velocity::MS(c).km_s()))
+    }
+
+    /// The orbit of `body` around `central` given its relative `position` and `velocity`.
+    pub fn from_state_vectors(central: Mass, body: Mass, position: SpatialVector, velocity: [Velocity; 3]) -> Result<Self, AstroError> {
+        let mu = mu(central, body)?;
+        let r = position.m().raw();
+        if let Some(v) = r.iter().find(|c| !c.is_finite()) {
+            return Err(AstroError::OutOfDomain { what: "position", value: *v });
+        }
+        let mut v = [0.0; 3];
+        for (v, c) in v.iter_mut().zip(velocity) {
+            *v = c.validate()?.m_s().raw();
+        }
+        let rn = dot(&r, &r).sqrt();
+        let h = cross(&r, &v);
+        let hn = dot(&h, &h).sqrt();
+        if rn == 0.0 || hn == 0.0 {
+            return Err(AstroError::OutOfDomain { what: "angular momentum", value: hn });
+        }
+        let hu = h.map(|c| c / hn);
+        let (v2, rv) = (dot(&v, &v), dot(&r, &v));
+        let ev = [0, 1, 2].map(|i| ((v2 - mu / rn) * r[i] - rv * v[i]) / mu);
+        let e = dot(&ev, &ev).sqrt();
+        let q = hn * hn / mu / (1.0 + e);
+        let inclination = Angle::Rad(hu[2].clamp(-1.0, 1.0).acos());
+        // Ascending node; along x for equatorial orbits.
+        let n = [-h[1], h[0], 0.0];
+        let nn = dot(&n, &n).sqrt();
+        let n = if nn > ECC_EPSILON * hn { n.map(|c| c / nn) } else { [1.0, 0.0, 0.0] };
+        let angle_in_plane = |from: &Vec3, to: &Vec3| Angle::Rad(dot(&hu, &cross(from, to)).atan2(dot(from, to)));
+        // Periapsis; at the node for circular orbits.
+        let (arg_periapsis, true_anomaly) = match e > ECC_EPSILON {
+            true => (angle_in_plane(&n, &ev), angle_in_plane(&ev, &r)),
+            false => (0.deg(), angle_in_plane(&n, &r))
+        };
+        let e = if (e - 1.0).abs() < ECC_EPSILON { 1.0 } else { e };
+        Self::from_parts(central, body, SpatialUnit::M(q).to_unit(&position.x()), e)
+            .with_orientation(inclination.deg(), Angle::Rad(n[1].atan2(n[0])).deg(), arg_periapsis.deg())
+            .with_true_anomaly(true_anomaly.deg())
+    }
+}
+
+fn cross(a: &Vec3, b: &Vec3) -> Vec3 {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+#[cfg(test)]
+mod orbit_tests {
+    use crate::{ApproxEq, AsMass, Tolerance};
+    use super::*;
+
+    #[test]
+    fn third_law() {
+        let yr = kepler_period(1.mo() + 1.me(), 1.au()).unwrap();
+        assert!(yr.approx_eq(&1.sidereal_yr(), Tolerance::relative(1e-4)), "{yr}");
+        let jupiter = Orbit::new(1.mo(), 1.mj(), 5.2026.au(), 0.0489).unwrap();
+        assert!(jupiter.period().unwrap().approx_eq(&11.862.yr(), Tolerance::relative(1e-3)));
+        assert!(kepler_period(0.kg(), 1.au()).is_err() && kepler_period(1.mo(), 0.au()).is_err());
+    }
+
+    #[test]
+    fn kepler_equation() {
+        for e in [0.0 as MetricsInternalType, 0.1, 0.5, 0.9, 0.999, 1.0, 1.5, 10.0] {
+            for nu in [-179.0 as MetricsInternalType, -90.0, -1.0, 0.0, 0.5, 45.0, 120.0, 179.0] {
+                // Hyperbolae only reach so far round.
+                if e > 1.0 && nu.abs() >= (-1.0 / e).acos().to_degrees() {
+                    continue;
+                }
+                let o = Orbit::parabolic(1.mo(), 0.kg(), 1.au()).unwrap();
+                let o = if e == 1.0 { o } else { Orbit::new(1.mo(), 0.kg(), (1.0 / (1.0 - e).abs()).au(), e).unwrap() };
+                let o = o.with_true_anomaly(nu.deg()).unwrap();
+                let back = solve_kepler(o.mean_anomaly(), e).unwrap();
+                assert!(back.approx_eq(&nu.deg(), Tolerance::absolute(1e-8)), "e={e} ν={nu}: {back}");
+            }
+        }
+        assert!(solve_kepler(1.rad(), -0.1).is_err());
+    }
+
+    #[test]
+    fn state_vector_round_trips() {
+        let cases = [
+            Orbit::new(1.mo(), 1.me(), 1.au(), 0.0167).unwrap().with_orientation(7.155.deg(), 174.9.deg(), 288.1.deg()).with_true_anomaly(33.deg()).unwrap(),
+            Orbit::new(1.mo(), 0.kg(), 2.au(), 1.8).unwrap().with_orientation(130.deg(), 10.deg(), 300.deg()).with_true_anomaly((-60).deg()).unwrap(),
+            Orbit::parabolic(1.mo(), 0.kg(), 0.5.au()).unwrap().with_orientation(45.deg(), 200.deg(), 20.deg()).with_true_anomaly(100.deg()).unwrap(),
+        ];
+        for o in cases {
+            let (r, v) = o.state_vectors();
+            let back = Orbit::from_state_vectors(o.central(), o.body(), r, v).unwrap();
+            assert!(back.periapsis().approx_eq(&o.periapsis(), Tolerance::relative(1e-9)));
+            assert!((back.eccentricity() - o.eccentricity()).abs() < 1e-9);
+            for (a, b) in [(back.inclination(), o.inclination()), (back.node(), o.node()), (back.arg_periapsis(), o.arg_periapsis()), (back.true_anomaly(), o.true_anomaly())] {
+                assert!(a.deg().approx_eq(&b, Tolerance::absolute(1e-7)), "{a} vs {b}");
+            }
+        }
+        // Vis-viva at 1 au on a circular orbit: ~29.78 km/s.
+        let (_, v) = Orbit::new(1.mo(), 0.kg(), 1.au(), 0.0).unwrap().state_vectors();
+        let speed = v.map(|c| c.km_s().raw()).iter().map(|c| c * c).sum::<MetricsInternalType>().sqrt();
+        assert!((speed - 29.785).abs() < 0.01, "{speed}");
+    }
+
+    #[test]
+    fn propagation() {
+        let o = Orbit::new(1.mo(), 0.kg(), 1.au(), 0.6).unwrap().with_true_anomaly(10.deg()).unwrap();
+        let full = o.propagate(o.period().unwrap()).unwrap();
+        assert!(full.true_anomaly().approx_eq(&o.true_anomaly(), Tolerance::absolute(1e-7)));
+        let half = o.propagate(o.period().unwrap() * 0.5).unwrap().propagate(o.period().unwrap() * -0.5).unwrap();
+        assert!(half.true_anomaly().approx_eq(&o.true_anomaly(), Tolerance::absolute(1e-7)));
+        // Past periapsis on a hyperbola, and on towards the asymptote.
+        let h = Orbit::new(1.mo(), 0.kg(), 1.au(), 2.0).unwrap().with_true_anomaly((-30).deg()).unwrap();
+        let later = h.propagate(1.yr()).unwrap();
+        assert!(later.true_anomaly().deg().normalized_signed() > 0.deg() && later.true_anomaly().deg().normalized_signed() < 120.deg());
+    }
+
+    #[test]
+    fn beyond_the_asymptote() {
+        // e = 2: the asymptotes sit at ν = ±120°.
+        let h = Orbit::new(1.mo(), 0.kg(), 1.au(), 2.0).unwrap();
+        for nu in [150.deg(), (-150).deg(), 121.deg(), 210.deg()] {
+            assert!(matches!(h.with_true_anomaly(nu), Err(AstroError::OutOfDomain { what: "true anomaly", .. })), "{nu}");
+        }
+        assert!(h.with_true_anomaly(119.deg()).is_ok() && h.with_true_anomaly(241.deg()).is_ok());
+        assert!(Orbit::new(1.mo(), 0.kg(), 1.au(), 0.5).unwrap().with_true_anomaly(150.deg()).is_ok());
+        assert!(Orbit::parabolic(1.mo(), 0.kg(), 1.au()).unwrap().with_true_anomaly(179.deg()).is_ok());
+    }
+}
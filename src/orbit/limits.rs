@@ -0,0 +1,114 @@
+//! Hill sphere, Roche limit and sphere of influence.
+use crate::{AsMass, AstroError, Checked, DefoAble, Density, Mass, MetricsInternalType, SpatialUnit};
+
+use super::Orbit;
+
+/// Rigidity of the satellite for [roche_limit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RocheModel {
+    /// A rigid, spherical body held together by its own gravity only - `1.26 R (ρ_M/ρ_m)^⅓`.
+    Rigid,
+    /// A fluid body free to deform into a tidally stretched ellipsoid - `2.44 R (ρ_M/ρ_m)^⅓`.
+    Fluid,
+}
+
+impl RocheModel {
+    /// The `k` in `k R (ρ_M/ρ_m)^⅓`.
+    pub fn coefficient(&self) -> MetricsInternalType {
+        match self {
+            Self::Rigid => 1.26,
+            Self::Fluid => 2.44,
+        }
+    }
+}
+
+/// `secondary / primary`, both validated and the primary non-zero.
+fn mass_ratio(primary: Mass, secondary: Mass) -> Result<MetricsInternalType, AstroError> {
+    let m = primary.validate()?.kg().raw();
+    if m == 0.0 {
+        return Err(AstroError::DivisionByZero);
+    }
+    Ok(secondary.validate()?.kg().raw() / m)
+}
+
+/// Hill radius of `secondary` orbiting `primary` at `semi_major_axis` with `eccentricity`, at periapsis -
+/// `a (1 - e) ∛(m / 3M)`. In the unit of `semi_major_axis`.
+pub fn hill_radius(primary: Mass, secondary: Mass, semi_major_axis: SpatialUnit, eccentricity: MetricsInternalType) -> Result<SpatialUnit, AstroError> {
+    if !(0.0..1.0).contains(&eccentricity) {
+        return Err(AstroError::OutOfDomain { what: "eccentricity", value: eccentricity });
+    }
+    let q = mass_ratio(primary, secondary)?;
+    Ok(semi_major_axis.validate()? * ((1.0 - eccentricity) * (q / 3.0).cbrt()))
+}
+
+/// Laplace's sphere of influence of `secondary` orbiting `primary` at `semi_major_axis` - `a (m / M)^⅖`.
+/// In the unit of `semi_major_axis`.
+pub fn sphere_of_influence(primary: Mass, secondary: Mass, semi_major_axis: SpatialUnit) -> Result<SpatialUnit, AstroError> {
+    let q = mass_ratio(primary, secondary)?;
+    Ok(semi_major_axis.validate()? * q.powf(0.4))
+}
+
+/// Roche limit of a satellite of `satellite_density` around a primary of `primary_radius` and `primary_density`.
+/// In the unit of `primary_radius`.
+pub fn roche_limit(primary_radius: SpatialUnit, primary_density: Density, satellite_density: Density, model: RocheModel) -> Result<SpatialUnit, AstroError> {
+    let rho_m = satellite_density.validate()?.raw();
+    if rho_m == 0.0 {
+        return Err(AstroError::DivisionByZero);
+    }
+    let ratio = primary_density.validate()?.cnv_into(&satellite_density).raw() / rho_m;
+    Ok(primary_radius.validate()? * (model.coefficient() * ratio.cbrt()))
+}
+
+/// [roche_limit] from masses and the satellite's radius - `k R_m ∛(M / m)`, the same thing as the density form.
+/// In the unit of `satellite_radius`.
+pub fn roche_limit_from_masses(primary: Mass, satellite: Mass, satellite_radius: SpatialUnit, model: RocheModel) -> Result<SpatialUnit, AstroError> {
+    let q = mass_ratio(satellite, primary)?;
+    Ok(satellite_radius.validate()? * (model.coefficient() * q.cbrt()))
+}
+
+impl Orbit {
+    /// Semi-major axis, if the orbit is an ellipse.
+    fn bound_semi_major_axis(&self) -> Result<SpatialUnit, AstroError> {
+        self.semi_major_axis().filter(|_| self.is_bound())
+            .ok_or(AstroError::OutOfDomain { what: "eccentricity", value: self.eccentricity() })
+    }
+
+    /// [hill_radius] of the orbiting body, for ellipses.
+    pub fn hill_radius(&self) -> Result<SpatialUnit, AstroError> {
+        hill_radius(self.central(), self.body(), self.bound_semi_major_axis()?, self.eccentricity())
+    }
+
+    /// [sphere_of_influence] of the orbiting body, for ellipses.
+    pub fn sphere_of_influence(&self) -> Result<SpatialUnit, AstroError> {
+        sphere_of_influence(self.central(), self.body(), self.bound_semi_major_axis()?)
+    }
+}
+
+#[cfg(test)]
+mod limits_tests {
+    use crate::{ApproxEq, AsCelestialRadii, AsDensity, AsSpatialUnit, Tolerance};
+    use super::*;
+
+    #[test]
+    fn earth() {
+        let earth = Orbit::new(1.mo(), 1.me(), 1.au(), 0.0167).unwrap();
+        // ~1.47 million km and ~925 000 km.
+        assert!((earth.hill_radius().unwrap().m().raw() / 1e9 - 1.47).abs() < 0.01, "{}", earth.hill_radius().unwrap());
+        assert!((earth.sphere_of_influence().unwrap().m().raw() / 1e9 - 0.925).abs() < 0.005);
+        let comet = Orbit::parabolic(1.mo(), 1e13.kg(), 1.au()).unwrap();
+        assert!(comet.hill_radius().is_err());
+        assert!(hill_radius(0.kg(), 1.me(), 1.au(), 0.0).is_err());
+    }
+
+    #[test]
+    fn roche() {
+        // Earth-Moon: rigid ~9 500 km, fluid ~18 400 km.
+        let rigid = roche_limit(1.re(), 5.514.g_cm3(), 3.344.g_cm3(), RocheModel::Rigid).unwrap();
+        let fluid = roche_limit(1.re(), 5514.kg_m3(), 3.344.g_cm3(), RocheModel::Fluid).unwrap();
+        assert!((rigid.m().raw() / 1e3 - 9_492.0).abs() < 20.0, "{}", rigid.m());
+        assert!((fluid.m().raw() / 1e3 - 18_381.0).abs() < 40.0, "{}", fluid.m());
+        let by_mass = roche_limit_from_masses(1.me(), 7.342e22.kg(), 1_737_400.m(), RocheModel::Rigid).unwrap();
+        assert!(by_mass.approx_eq(&rigid, Tolerance::relative(2e-3)));
+        assert!(roche_limit(1.re(), 1.g_cm3(), 0.g_cm3(), RocheModel::Fluid).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+//! Parallax ↔ distance
+//! 
+//! Inverting a parallax is only fine for precise, positive ones. For everything else (low S/N, negative, …)
+//! there's the exponentially decreasing space density (EDSD) prior of Bailer-Jones (2015, PASP 127, 994).
+use crate::{Angle, AsAngle, AsSpatialUnit, AstroError, Checked, DefoAble, MetricsInternalType, SpatialUnit};
+
+/// EDSD length scale used by [DistanceEstimator::edsd], in kpc - Astraatmadja & Bailer-Jones (2016, ApJ 833, 119).
+pub const EDSD_LENGTH_SCALE_KPC: MetricsInternalType = 1.35;
+
+/// A measured parallax with its (1σ) uncertainty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parallax {
+    /// Measured parallax; may be negative.
+    pub value: Angle,
+    /// Standard uncertainty of the measurement.
+    pub uncertainty: Angle,
+}
+
+/// How to turn a [Parallax] into a distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistanceEstimator {
+    /// `d = 1/ϖ`. Only for positive parallaxes, and really only for fractional errors below ~10 %.
+    Naive,
+    /// Mode of the posterior with an exponentially decreasing space density prior of the given length scale.
+    ExpDecreasing { length_scale: SpatialUnit },
+}
+
+impl DistanceEstimator {
+    /// [DistanceEstimator::ExpDecreasing] with the default [EDSD_LENGTH_SCALE_KPC].
+    pub fn edsd() -> Self {
+        Self::ExpDecreasing { length_scale: SpatialUnit::Pc(EDSD_LENGTH_SCALE_KPC * 1e3) }
+    }
+}
+
+/// A distance estimate with its 68 % (15.87…84.13 percentile) bounds, all in [SpatialUnit::Pc].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistanceEstimate {
+    /// Point estimate.
+    pub distance: SpatialUnit,
+    /// Lower bound.
+    pub lower: SpatialUnit,
+    /// Upper bound, possibly ∞.
+    pub upper: SpatialUnit,
+}
+
+impl Parallax {
+    /// A parallax of `value` ± `uncertainty`.
+    pub fn new(value: Angle, uncertainty: Angle) -> Self {
+        Self { value, uncertainty }
+    }
+
+    /// Expected (error-free) parallax of an object at `distance`.
+    pub fn from_distance(distance: SpatialUnit) -> Result<Self, AstroError> {
+        Ok(Self::new(distance.validate()?.parallax()?, Angle::Mas(0.0)))
+    }
+
+    /// Signal-to-noise ratio, `ϖ/σ`.
+    pub fn snr(&self) -> MetricsInternalType {
+        self.value / self.uncertainty
+    }
+
+    /// Distance by the chosen `estimator`.
+    pub fn distance(&self, estimator: DistanceEstimator) -> Result<DistanceEstimate, AstroError> {
+        let w = self.value.validate()?.mas().raw();
+        let s = self.uncertainty.validate()?.mas().raw().abs();
+        match estimator {
+            DistanceEstimator::Naive => naive(w, s),
+            DistanceEstimator::ExpDecreasing { length_scale } => {
+                let l = length_scale.validate()?.pc().raw() / 1e3;
+                if l == 0.0 {
+                    return Err(AstroError::OutOfDomain { what: "EDSD length scale", value: l });
+                }
+                if s == 0.0 {
+                    return naive(w, s);
+                }
+                Ok(edsd(w, s, l))
+            }
+        }
+    }
+}
+
+impl SpatialUnit {
+    /// Parallax of an object at distance `self`.
+    pub fn parallax(&self) -> Result<Angle, AstroError> {
+        match self.pc().raw() {
+            0.0 => Err(AstroError::DivisionByZero),
+            d => Ok(Angle::Mas(1e3 / d))
+        }
+    }
+}
+
+/// Plain inversion, bounds from `ϖ ± σ`.
+fn naive(w: MetricsInternalType, s: MetricsInternalType) -> Result<DistanceEstimate, AstroError> {
+    if w <= 0.0 {
+        return Err(AstroError::OutOfDomain { what: "parallax (naive inversion)", value: w });
+    }
+    let upper = if w > s { 1e3 / (w - s) } else { MetricsInternalType::INFINITY };
+    Ok(DistanceEstimate {
+        distance: SpatialUnit::Pc(1e3 / w),
+        lower: SpatialUnit::Pc(1e3 / (w + s)),
+        upper: SpatialUnit::Pc(upper),
+    })
+}
+
+/// EDSD posterior mode + percentiles; `w`, `s` in mas, `l` in kpc (so 1/r is in mas).
+fn edsd(w: MetricsInternalType, s: MetricsInternalType, l: MetricsInternalType) -> DistanceEstimate {
+    // ln P(r | ϖ) up to a constant: r² e^(-r/L) e^(-(ϖ - 1/r)²/2σ²)
+    let ln_p = |r: MetricsInternalType| 2.0 * r.ln() - r / l - (w - 1.0 / r).powi(2) / (2.0 * s * s);
+    let mode = edsd_mode(w, s, l);
+    let peak = ln_p(mode);
+    // Integration range - centred on the mode, sized by the posterior's width there (Laplace approximation,
+    // σ_r = (-d²ln P/dr²)^-½), and stepped out to where the posterior has dropped by e⁻⁴⁰.
+    let curvature = -2.0 / mode.powi(2) - (1.0 / mode.powi(4) - 2.0 * (w - 1.0 / mode) / mode.powi(3)) / (s * s);
+    let width = if curvature < 0.0 { (-1.0 / curvature).sqrt() } else { mode };
+    let mut hi = mode + 6.0 * width;
+    while ln_p(hi) > peak - 40.0 { hi = mode + 2.0 * (hi - mode) }
+    let mut lo = (mode - 6.0 * width).max(0.0);
+    while lo > 0.0 && ln_p(lo) > peak - 40.0 { lo = (mode - 2.0 * (mode - lo)).max(0.0) }
+    // Trapezoid over a uniform grid, accumulating the CDF as we go.
+    const N: usize = 20_000;
+    let h = (hi - lo) / N as MetricsInternalType;
+    let p = |i: usize| (ln_p(lo + h * i as MetricsInternalType) - peak).exp();
+    let mut cdf = Vec::with_capacity(N + 1);
+    cdf.push(0.0);
+    for i in 1..=N {
+        cdf.push(cdf[i - 1] + 0.5 * h * (p(i - 1) + p(i)));
+    }
+    let total = cdf[N];
+    let quantile = |q: MetricsInternalType| {
+        let target = q * total;
+        let i = cdf.partition_point(|c| *c < target).clamp(1, N);
+        let (c0, c1) = (cdf[i - 1], cdf[i]);
+        let frac = if c1 > c0 { (target - c0) / (c1 - c0) } else { 0.0 };
+        lo + h * ((i - 1) as MetricsInternalType + frac)
+    };
+    DistanceEstimate {
+        distance: SpatialUnit::Pc(mode * 1e3),
+        lower: SpatialUnit::Pc(quantile(0.158_655_25) * 1e3),
+        upper: SpatialUnit::Pc(quantile(0.841_344_75) * 1e3),
+    }
+}
+
+/// Mode of the EDSD posterior: the real root of `r³/L - 2r² + (ϖ/σ²)r - 1/σ² = 0` (Bailer-Jones 2015, eq. 19)
+/// with the highest posterior.
+fn edsd_mode(w: MetricsInternalType, s: MetricsInternalType, l: MetricsInternalType) -> MetricsInternalType {
+    let s2 = s * s;
+    let f = |r: MetricsInternalType| r * r * r / l - 2.0 * r * r + w / s2 * r - 1.0 / s2;
+    let df = |r: MetricsInternalType| 3.0 * r * r / l - 4.0 * r + w / s2;
+    let ln_p = |r: MetricsInternalType| 2.0 * r.ln() - r / l - (w - 1.0 / r).powi(2) / (2.0 * s2);
+    // f(0) < 0 and f(∞) > 0, so there's at least one positive root. Bracket them all by sign changes on
+    // a log grid, polish with bisection, and pick the most probable.
+    let grid = |i: i32| 1e-6 * l * MetricsInternalType::powf(10.0, i as MetricsInternalType / 100.0);
+    let mut best: Option<MetricsInternalType> = None;
+    for i in 0..1000 {
+        let (mut a, mut b) = (grid(i), grid(i + 1));
+        if f(a).signum() == f(b).signum() {
+            continue;
+        }
+        for _ in 0..200 {
+            let m = 0.5 * (a + b);
+            if f(a).signum() == f(m).signum() { a = m } else { b = m }
+        }
+        // One Newton step to polish.
+        let mut r = 0.5 * (a + b);
+        if df(r) != 0.0 {
+            let n = r - f(r) / df(r);
+            if n > a && n < b { r = n }
+        }
+        if best.is_none_or(|x| ln_p(r) > ln_p(x)) {
+            best = Some(r);
+        }
+    }
+    best.unwrap_or(2.0 * l)
+}
+
+#[cfg(test)]
+mod parallax_tests {
+    use crate::{ApproxEq, Tolerance, assert_quantity_eq};
+    use super::*;
+
+    #[test]
+    fn naive_inversion() {
+        let p = Parallax::new(100.mas(), 1.mas());
+        let d = p.distance(DistanceEstimator::Naive).unwrap();
+        assert_eq!(10.pc(), d.distance);
+        assert!(d.lower < d.distance && d.distance < d.upper);
+        assert!(Parallax::new((-1).mas(), 1.mas()).distance(DistanceEstimator::Naive).is_err());
+        let d = Parallax::new(1.mas(), 2.mas()).distance(DistanceEstimator::Naive).unwrap();
+        assert!(d.upper.raw().is_infinite());
+    }
+
+    #[test]
+    fn edsd_prior() {
+        // High S/N - the prior hardly matters.
+        for s in [0.01, 0.001, 0.0001] {
+            let d = Parallax::new(100.mas(), s.mas()).distance(DistanceEstimator::edsd()).unwrap();
+            assert!(d.distance.approx_eq(&10.pc(), Tolerance::relative(1e-7)), "σ = {s}: {}", d.distance);
+            assert!(d.lower.approx_eq(&(1e3 / (100.0 + s)).pc(), Tolerance::relative(1e-7)), "σ = {s}: {}", d.lower);
+            assert!(d.upper.approx_eq(&(1e3 / (100.0 - s)).pc(), Tolerance::relative(1e-7)), "σ = {s}: {}", d.upper);
+        }
+        // Negative parallax - still a finite, sensible estimate.
+        let d = Parallax::new((-0.5).mas(), 1.mas()).distance(DistanceEstimator::edsd()).unwrap();
+        assert!(d.distance.pc().raw().is_finite() && d.distance > 0.pc());
+        assert!(d.lower < d.distance && d.distance < d.upper);
+        // …and as σ → ∞ the posterior tends to the prior, with its mode at 2L.
+        let d = Parallax::new(0.mas(), 1e6.mas()).distance(DistanceEstimator::edsd()).unwrap();
+        assert!(d.distance.approx_eq(&2700.pc(), Tolerance::relative(1e-3)));
+    }
+
+    #[test]
+    fn back_to_parallax() {
+        assert_quantity_eq!(100.mas(), 10.pc().parallax().unwrap());
+        assert_quantity_eq!(1.arcsec(), 1.pc().parallax().unwrap());
+        assert_quantity_eq!(1.pc(), Parallax::from_distance(1.pc()).unwrap().distance(DistanceEstimator::Naive).unwrap().distance);
+        assert!(0.pc().parallax().is_err());
+    }
+}
@@ -0,0 +1,157 @@
+//! Parsing
+//! 
+//! The shared bits of the `FromStr` impls - "1.5 M☉", "3ly", "5778 K", "12 R⊕", …
+use std::fmt::Display;
+
+use crate::MetricsInternalType;
+
+/// What went wrong while parsing a metric.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMetricErrorKind {
+    /// Nothing (but whitespace) to parse.
+    Empty,
+    /// The numeric part is missing or malformed.
+    InvalidNumber,
+    /// A number, but no unit after it.
+    MissingUnit,
+    /// A unit nobody has heard of (at least not this metric).
+    UnknownUnit(String),
+    /// A number the metric can't take, e.g. a temperature below absolute zero.
+    OutOfRange,
+}
+
+/// A typed parse error with the (byte) position of the problem within the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMetricError {
+    kind: ParseMetricErrorKind,
+    position: usize,
+}
+
+impl ParseMetricError {
+    pub(crate) fn new(kind: ParseMetricErrorKind, position: usize) -> Self {
+        Self { kind, position }
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ParseMetricErrorKind {
+        &self.kind
+    }
+
+    /// Byte offset of the problem within the input.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseMetricError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ParseMetricErrorKind::Empty => write!(f, "nothing to parse"),
+            ParseMetricErrorKind::InvalidNumber => write!(f, "invalid number at position {}", self.position),
+            ParseMetricErrorKind::MissingUnit => write!(f, "missing unit at position {}", self.position),
+            ParseMetricErrorKind::UnknownUnit(u) => write!(f, "unknown unit '{u}' at position {}", self.position),
+            ParseMetricErrorKind::OutOfRange => write!(f, "value out of range at position {}", self.position),
+        }
+    }
+}
+
+impl std::error::Error for ParseMetricError {}
+
+/// A unit table row: accepted symbol and how to turn a value into the metric.
+pub(crate) type UnitRow<T> = (&'static str, fn(MetricsInternalType) -> T);
+
+/// Scan a (possibly signed, possibly scientific) number from the start of `s`.
+/// 
+/// Returns the number and how many bytes of `s` it took.
+pub(crate) fn scan_number(s: &str) -> Option<(MetricsInternalType, usize)> {
+    let b = s.as_bytes();
+    let digits = |mut i: usize| { while i < b.len() && b[i].is_ascii_digit() { i += 1 } i };
+    let mut i = 0;
+    if i < b.len() && (b[i] == b'+' || b[i] == b'-') { i += 1 }
+    let int_end = digits(i);
+    let mut end = int_end;
+    let mut frac_digits = 0;
+    if end < b.len() && b[end] == b'.' {
+        let frac_end = digits(end + 1);
+        frac_digits = frac_end - end - 1;
+        end = frac_end;
+    }
+    if int_end == i && frac_digits == 0 {
+        return None;
+    }
+    // Exponent only counts if there are digits after it - "1erg/s" is not "1e…".
+    if end < b.len() && (b[end] == b'e' || b[end] == b'E') {
+        let mut j = end + 1;
+        if j < b.len() && (b[j] == b'+' || b[j] == b'-') { j += 1 }
+        let exp_end = digits(j);
+        if exp_end > j {
+            end = exp_end;
+        }
+    }
+    s[..end].parse::<MetricsInternalType>().ok().map(|v| (v, end))
+}
+
+/// Find `unit` from `units`, exact match first and then ASCII case-insensitively.
+fn lookup<T>(unit: &str, units: &[UnitRow<T>], exact: bool) -> Option<fn(MetricsInternalType) -> T> {
+    units.iter().find(|(sym, _)| *sym == unit)
+        .or_else(|| if exact { None } else { units.iter().find(|(sym, _)| sym.eq_ignore_ascii_case(unit)) })
+        .map(|(_, ctor)| *ctor)
+}
+
+/// Parse `<number> [whitespace] <unit>` using the given unit table.
+pub(crate) fn parse_metric<T>(s: &str, units: &[UnitRow<T>]) -> Result<T, ParseMetricError> {
+    parse_with(s, units, false)
+}
+
+/// As [parse_metric], but without the case-insensitive unit fallback -
+/// for tables where case alone tells units apart ("mag" vs. "Mag").
+pub(crate) fn parse_metric_exact<T>(s: &str, units: &[UnitRow<T>]) -> Result<T, ParseMetricError> {
+    parse_with(s, units, true)
+}
+
+fn parse_with<T>(s: &str, units: &[UnitRow<T>], exact: bool) -> Result<T, ParseMetricError> {
+    let start = s.len() - s.trim_start().len();
+    let body = s.trim();
+    if body.is_empty() {
+        return Err(ParseMetricError::new(ParseMetricErrorKind::Empty, 0));
+    }
+    let (value, n_len) = scan_number(body)
+        .ok_or(ParseMetricError::new(ParseMetricErrorKind::InvalidNumber, start))?;
+    let rest = &body[n_len..];
+    let unit = rest.trim_start();
+    let unit_pos = start + n_len + (rest.len() - unit.len());
+    if unit.is_empty() {
+        return Err(ParseMetricError::new(ParseMetricErrorKind::MissingUnit, unit_pos));
+    }
+    lookup(unit, units, exact)
+        .map(|ctor| ctor(value))
+        .ok_or(ParseMetricError::new(ParseMetricErrorKind::UnknownUnit(unit.to_string()), unit_pos))
+}
+
+#[cfg(test)]
+mod parse_tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Some((1.5, 3)), scan_number("1.5 kg"));
+        assert_eq!(Some((-2e-3, 5)), scan_number("-2e-3K"));
+        assert_eq!(Some((1.0, 1)), scan_number("1erg/s"));
+        assert_eq!(Some((0.5, 2)), scan_number(".5"));
+        assert_eq!(None, scan_number("kg"));
+        assert_eq!(None, scan_number("-."));
+    }
+
+    #[test]
+    fn error_positions() {
+        let units: &[UnitRow<MetricsInternalType>] = &[("x", |v| v)];
+        assert_eq!(Ok(3.0), parse_metric(" 3 x ", units));
+        let e = parse_metric("  3 y", units).unwrap_err();
+        assert_eq!((&ParseMetricErrorKind::UnknownUnit("y".into()), 4), (e.kind(), e.position()));
+        let e = parse_metric("  3 ", units).unwrap_err();
+        assert_eq!((&ParseMetricErrorKind::MissingUnit, 3), (e.kind(), e.position()));
+        let e = parse_metric(" ?x", units).unwrap_err();
+        assert_eq!((&ParseMetricErrorKind::InvalidNumber, 1), (e.kind(), e.position()));
+        assert_eq!(ParseMetricErrorKind::Empty, *parse_metric("  ", units).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,285 @@
+//! Quantity
+//! 
+//! A runtime-dimensioned value, for when the closed metric enums just won't cut it
+//! (formulas read from config files and the like).
+//! 
+//! Values are always kept in coherent SI units - kg, m, s, K, …
+use std::{cmp::Ordering, fmt::Display, ops::{Add, Div, Mul, Neg, Sub}};
+
+use serde::{Deserialize, Serialize};
+
+mod dimension;
+pub use dimension::{BaseDimension, Dimension};
+use crate::{Acceleration, Angle, Area, AsAcceleration, AsAngle, AsArea, AsDensity, AsLuminosity, AsMass, AsSpatialUnit, AsTemperature, AsTime, AsVelocity, AsVolume, DefoAble, Density, Luminosity, Mass, MetricsInternalType, SpatialUnit, Temperature, Time, Velocity, Volume};
+
+/// Things that can go wrong with runtime dimensional analysis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuantityError {
+    /// Dimensions don't agree, e.g. `m + kg`.
+    DimensionMismatch { lhs: Dimension, rhs: Dimension },
+    /// A root that doesn't come out even, e.g. `√m`.
+    UnevenRoot { dimension: Dimension, root: i8 },
+    /// A dimension exponent that won't fit an `i8`, e.g. `m¹²⁷·m`.
+    ExponentOverflow { dimension: Dimension },
+}
+
+impl Display for QuantityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DimensionMismatch { lhs, rhs } => write!(f, "dimension mismatch: [{lhs}] vs [{rhs}]"),
+            Self::UnevenRoot { dimension, root } => write!(f, "cannot take root {root} of [{dimension}]"),
+            Self::ExponentOverflow { dimension } => write!(f, "dimension exponent overflow in [{dimension}]"),
+        }
+    }
+}
+
+impl std::error::Error for QuantityError {}
+
+/// A value (in SI) with its base-dimension exponents.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    value: MetricsInternalType,
+    dimension: Dimension,
+}
+
+impl Quantity {
+    /// A new quantity; `value` is expected to be in coherent SI units.
+    pub const fn new(value: MetricsInternalType, dimension: Dimension) -> Self {
+        Self { value, dimension }
+    }
+
+    /// A pure number.
+    pub const fn dimensionless(value: MetricsInternalType) -> Self {
+        Self::new(value, Dimension::DIMENSIONLESS)
+    }
+
+    /// The SI value.
+    pub const fn value(&self) -> MetricsInternalType {
+        self.value
+    }
+
+    /// The dimension.
+    pub const fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    /// Check that `self` has the `expected` dimension.
+    pub fn expect_dimension(&self, expected: Dimension) -> Result<&Self, QuantityError> {
+        if self.dimension == expected {
+            Ok(self)
+        } else {
+            Err(QuantityError::DimensionMismatch { lhs: self.dimension, rhs: expected })
+        }
+    }
+
+    /// `self` raised to the `n`th power, if the dimension exponents don't overflow.
+    pub fn powi(&self, n: i8) -> Result<Self, QuantityError> {
+        let dimension = self.dimension.powi(n).ok_or(QuantityError::ExponentOverflow { dimension: self.dimension })?;
+        Ok(Self::new(self.value.powi(n as i32), dimension))
+    }
+
+    /// `n`th root of `self`, if the dimension allows for it.
+    pub fn root(&self, n: i8) -> Result<Self, QuantityError> {
+        let dimension = self.dimension.root(n).ok_or(QuantityError::UnevenRoot { dimension: self.dimension, root: n })?;
+        Ok(Self::new(self.value.powf(1.0 / n as MetricsInternalType), dimension))
+    }
+
+    /// Square root of `self`, if the dimension allows for it.
+    pub fn sqrt(&self) -> Result<Self, QuantityError> {
+        self.root(2)
+    }
+}
+
+/// `Add` and `Sub` check dimensions, hence `Result` as output.
+macro_rules! quantity_checked_op {
+    ($trait:ident $fn:ident) => {
+        impl $trait<&Quantity> for &Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: &Quantity) -> Self::Output {
+                if self.dimension != rhs.dimension {
+                    return Err(QuantityError::DimensionMismatch { lhs: self.dimension, rhs: rhs.dimension });
+                }
+                Ok(Quantity::new(self.value.$fn(rhs.value), self.dimension))
+            }
+        }
+        impl $trait<Quantity> for &Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: Quantity) -> Self::Output {<&Quantity as $trait<&Quantity>>::$fn(self, &rhs)}
+        }
+        impl $trait<&Quantity> for Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: &Quantity) -> Self::Output {<&Quantity as $trait<&Quantity>>::$fn(&self, rhs)}
+        }
+        impl $trait<Quantity> for Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: Quantity) -> Self::Output {<&Quantity as $trait<&Quantity>>::$fn(&self, &rhs)}
+        }
+    };
+}
+
+/// `Mul` and `Div` combine the dimensions - `Result`, as the exponents might overflow.
+macro_rules! quantity_op {
+    ($trait:ident $fn:ident) => {
+        impl $trait<&Quantity> for &Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: &Quantity) -> Self::Output {
+                let dimension = self.dimension.$fn(rhs.dimension)
+                    .ok_or(QuantityError::ExponentOverflow { dimension: self.dimension })?;
+                Ok(Quantity::new(self.value.$fn(rhs.value), dimension))
+            }
+        }
+        impl $trait<Quantity> for &Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: Quantity) -> Self::Output {<&Quantity as $trait<&Quantity>>::$fn(self, &rhs)}
+        }
+        impl $trait<&Quantity> for Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: &Quantity) -> Self::Output {<&Quantity as $trait<&Quantity>>::$fn(&self, rhs)}
+        }
+        impl $trait<Quantity> for Quantity {
+            type Output = Result<Quantity, QuantityError>;
+            fn $fn(self, rhs: Quantity) -> Self::Output {<&Quantity as $trait<&Quantity>>::$fn(&self, &rhs)}
+        }
+        // Scaling by a pure number.
+        impl $trait<MetricsInternalType> for Quantity {
+            type Output = Quantity;
+            fn $fn(self, rhs: MetricsInternalType) -> Self::Output {
+                Quantity::new(self.value.$fn(rhs), self.dimension)
+            }
+        }
+        impl $trait<MetricsInternalType> for &Quantity {
+            type Output = Quantity;
+            fn $fn(self, rhs: MetricsInternalType) -> Self::Output {<Quantity as $trait<MetricsInternalType>>::$fn(*self, rhs)}
+        }
+    };
+}
+
+quantity_checked_op!(Add add);
+quantity_checked_op!(Sub sub);
+quantity_op!(Mul mul);
+quantity_op!(Div div);
+
+impl Neg for Quantity {
+    type Output = Quantity;
+    fn neg(self) -> Self::Output {
+        Quantity::new(-self.value, self.dimension)
+    }
+}
+
+impl PartialOrd for Quantity {
+    /// Quantities of differing dimensions are unordered.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.dimension != other.dimension {
+            return None;
+        }
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "{:.*}", p, self.value)?,
+            None => write!(f, "{}", self.value)?,
+        }
+        if self.dimension.is_dimensionless() {
+            return Ok(());
+        }
+        write!(f, " {}", self.dimension)
+    }
+}
+
+impl From<MetricsInternalType> for Quantity {
+    fn from(value: MetricsInternalType) -> Self {
+        Self::dimensionless(value)
+    }
+}
+
+/// `From<$metric> for Quantity` and `TryFrom<Quantity> for $metric`.
+macro_rules! quantity_conversions {
+    ($($metric:ident: $dim:ident, |$m:ident| $to_si:expr, |$v:ident| $from_si:expr);+ $(;)?) => {$(
+        impl From<&$metric> for Quantity {
+            fn from($m: &$metric) -> Self {
+                Self::new($to_si, Dimension::$dim)
+            }
+        }
+        impl From<$metric> for Quantity {
+            fn from(value: $metric) -> Self {<Quantity as From<&$metric>>::from(&value)}
+        }
+        impl TryFrom<&Quantity> for $metric {
+            type Error = QuantityError;
+            fn try_from(value: &Quantity) -> Result<Self, Self::Error> {
+                let $v = value.expect_dimension(Dimension::$dim)?.value;
+                Ok($from_si)
+            }
+        }
+        impl TryFrom<Quantity> for $metric {
+            type Error = QuantityError;
+            fn try_from(value: Quantity) -> Result<Self, Self::Error> {<$metric as TryFrom<&Quantity>>::try_from(&value)}
+        }
+    )*};
+}
+
+quantity_conversions! {
+    Mass: MASS, |m| m.kg().raw(), |v| Mass::Kg(v);
+    SpatialUnit: LENGTH, |s| s.m().raw(), |v| SpatialUnit::M(v);
+    // Black holes come out as NaN K…
+    Temperature: TEMPERATURE, |t| t.k().raw(), |v| Temperature::K(v);
+    Area: AREA, |a| a.m2().raw(), |v| Area::M2(v);
+    Volume: VOLUME, |a| a.m3().raw(), |v| Volume::M3(v);
+    Density: DENSITY, |d| d.kg_m3().raw(), |v| Density::KgM3(v);
+    Time: TIME, |t| t.s().raw(), |v| Time::S(v);
+    Velocity: VELOCITY, |u| u.m_s().raw(), |v| Velocity::MS(v);
+    Acceleration: ACCELERATION, |a| a.m_s2().raw(), |v| Acceleration::MS2(v);
+    Luminosity: POWER, |l| l.w().raw(), |v| Luminosity::W(v);
+    // Radians are a (dimensionless) ratio.
+    Angle: DIMENSIONLESS, |a| a.rad().raw(), |v| Angle::Rad(v);
+}
+
+#[cfg(test)]
+mod quantity_tests {
+    use crate::{AsMass, AsSpatialUnit, AsTemperature};
+    use super::*;
+
+    #[test]
+    fn dimension_checks() {
+        let m = Quantity::from(2.m());
+        let kg = Quantity::from(3.kg());
+        assert_eq!(Err(QuantityError::DimensionMismatch { lhs: Dimension::LENGTH, rhs: Dimension::MASS }), m + kg);
+        assert_eq!(Ok(Quantity::new(4.0, Dimension::LENGTH)), m + m);
+        assert_eq!(Dimension::DENSITY, (kg / m.powi(3).unwrap()).unwrap().dimension());
+        assert!(m.sqrt().is_err());
+        assert_eq!(Ok(m), (m * m).unwrap().sqrt());
+    }
+
+    #[test]
+    fn exponent_overflow() {
+        let big = Quantity::new(1.0, Dimension::new([127, 0, 0, 0, 0, 0, 0]));
+        let m = Quantity::from(1.m());
+        assert_eq!(Err(QuantityError::ExponentOverflow { dimension: big.dimension() }), big * m);
+        assert_eq!(Err(QuantityError::ExponentOverflow { dimension: big.dimension() }), big.powi(2));
+        assert!(big.powi(-1).is_ok());
+        let small = Quantity::new(1.0, Dimension::new([-128, 0, 0, 0, 0, 0, 0]));
+        assert!((small / m).is_err());
+        assert!(small.powi(-1).is_err());
+        assert!(small.root(-1).is_err());
+        assert_eq!(Ok(Quantity::new(1.0, Dimension::new([64, 0, 0, 0, 0, 0, 0]))), small.root(-2));
+    }
+
+    #[test]
+    fn round_trips() {
+        let q = Quantity::from(1.au());
+        assert_eq!(1.au(), SpatialUnit::try_from(q).unwrap());
+        assert!(Mass::try_from(q).is_err());
+        let rho = (Quantity::from(1.me()) / Quantity::from(1.m()).powi(3).unwrap()).unwrap();
+        assert_eq!(1.me() / 1.m3(), Density::try_from(rho).unwrap());
+        assert_eq!(300.k(), Temperature::try_from(Quantity::from(300.k())).unwrap());
+    }
+
+    #[test]
+    fn display() {
+        let rho = Quantity::new(5514.0, Dimension::DENSITY);
+        assert_eq!("5514.0 kg·m⁻³", format!("{rho:.1}"));
+        assert_eq!("0.5", Quantity::dimensionless(0.5).to_string());
+    }
+}
@@ -0,0 +1,167 @@
+//! Dimension
+//! 
+//! SI base-dimension exponents, i.e. the L, M, T, I, Θ, N, J of a [Quantity][crate::Quantity].
+use std::{fmt::Display, ops::{Div, Mul}};
+
+use serde::{Deserialize, Serialize};
+
+/// The seven SI base dimensions.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BaseDimension {
+    /// L - length, m.
+    Length,
+    /// M - mass, kg.
+    Mass,
+    /// T - time, s.
+    Time,
+    /// I - electric current, A.
+    Current,
+    /// Θ - thermodynamic temperature, K.
+    Temperature,
+    /// N - amount of substance, mol.
+    Amount,
+    /// J - luminous intensity, cd.
+    LuminousIntensity,
+}
+
+impl BaseDimension {
+    /// All base dimensions, in exponent vector order.
+    pub const ALL: [BaseDimension; 7] = [
+        Self::Length, Self::Mass, Self::Time, Self::Current,
+        Self::Temperature, Self::Amount, Self::LuminousIntensity,
+    ];
+
+    /// The SI base unit symbol.
+    pub fn si_symbol(&self) -> &'static str {
+        match self {
+            Self::Length => "m",
+            Self::Mass => "kg",
+            Self::Time => "s",
+            Self::Current => "A",
+            Self::Temperature => "K",
+            Self::Amount => "mol",
+            Self::LuminousIntensity => "cd",
+        }
+    }
+}
+
+/// A vector of base-dimension exponents.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension([i8; 7]);
+
+impl Dimension {
+    /// Pure number, no dimension at all.
+    pub const DIMENSIONLESS: Dimension = Dimension([0, 0, 0, 0, 0, 0, 0]);
+    /// L
+    pub const LENGTH: Dimension = Dimension([1, 0, 0, 0, 0, 0, 0]);
+    /// M
+    pub const MASS: Dimension = Dimension([0, 1, 0, 0, 0, 0, 0]);
+    /// T
+    pub const TIME: Dimension = Dimension([0, 0, 1, 0, 0, 0, 0]);
+    /// Θ
+    pub const TEMPERATURE: Dimension = Dimension([0, 0, 0, 0, 1, 0, 0]);
+    /// L²
+    pub const AREA: Dimension = Dimension([2, 0, 0, 0, 0, 0, 0]);
+    /// L³
+    pub const VOLUME: Dimension = Dimension([3, 0, 0, 0, 0, 0, 0]);
+    /// M·L⁻³
+    pub const DENSITY: Dimension = Dimension([-3, 1, 0, 0, 0, 0, 0]);
+    /// L·T⁻¹
+    pub const VELOCITY: Dimension = Dimension([1, 0, -1, 0, 0, 0, 0]);
+    /// L·T⁻²
+    pub const ACCELERATION: Dimension = Dimension([1, 0, -2, 0, 0, 0, 0]);
+    /// L²·M·T⁻³
+    pub const POWER: Dimension = Dimension([2, 1, -3, 0, 0, 0, 0]);
+
+    /// Construct from raw exponents, in [BaseDimension::ALL] order.
+    pub const fn new(exponents: [i8; 7]) -> Self {
+        Self(exponents)
+    }
+
+    /// Exponent of the given base dimension.
+    pub const fn exponent(&self, base: BaseDimension) -> i8 {
+        self.0[base as usize]
+    }
+
+    /// Raw exponents, in [BaseDimension::ALL] order.
+    pub const fn exponents(&self) -> [i8; 7] {
+        self.0
+    }
+
+    /// Is this a pure number?
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::DIMENSIONLESS
+    }
+
+    /// Dimension raised to the `n`th power, if the exponents stay within `i8`.
+    pub fn powi(&self, n: i8) -> Option<Self> {
+        let mut e = self.0;
+        for x in e.iter_mut() {
+            *x = x.checked_mul(n)?;
+        }
+        Some(Self(e))
+    }
+
+    /// `n`th root of the dimension, if all the exponents divide evenly.
+    pub fn root(&self, n: i8) -> Option<Self> {
+        let mut e = self.0;
+        for x in e.iter_mut() {
+            if x.checked_rem(n)? != 0 {
+                return None;
+            }
+            *x = x.checked_div(n)?;
+        }
+        Some(Self(e))
+    }
+}
+
+/// `Mul` and `Div` yield `None` if an exponent would overflow `i8`.
+impl Mul for Dimension {
+    type Output = Option<Dimension>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let mut e = self.0;
+        for (a, b) in e.iter_mut().zip(rhs.0) {
+            *a = a.checked_add(b)?;
+        }
+        Some(Self(e))
+    }
+}
+
+impl Div for Dimension {
+    type Output = Option<Dimension>;
+    fn div(self, rhs: Self) -> Self::Output {
+        let mut e = self.0;
+        for (a, b) in e.iter_mut().zip(rhs.0) {
+            *a = a.checked_sub(b)?;
+        }
+        Some(Self(e))
+    }
+}
+
+/// Unicode superscript for an exponent.
+fn superscript(e: i8) -> String {
+    e.to_string().chars().map(|c| match c {
+        '-' => '⁻',
+        '0' => '⁰', '1' => '¹', '2' => '²', '3' => '³', '4' => '⁴',
+        '5' => '⁵', '6' => '⁶', '7' => '⁷', '8' => '⁸', _ => '⁹',
+    }).collect()
+}
+
+impl Display for Dimension {
+    /// Writes the dimension in SI base units, e.g. `kg·m⁻³`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Customary-ish order: kg m s A K mol cd
+        const ORDER: [BaseDimension; 7] = [
+            BaseDimension::Mass, BaseDimension::Length, BaseDimension::Time, BaseDimension::Current,
+            BaseDimension::Temperature, BaseDimension::Amount, BaseDimension::LuminousIntensity,
+        ];
+        let parts: Vec<String> = ORDER.iter()
+            .filter(|b| self.exponent(**b) != 0)
+            .map(|b| match self.exponent(*b) {
+                1 => b.si_symbol().to_string(),
+                e => format!("{}{}", b.si_symbol(), superscript(e)),
+            })
+            .collect();
+        write!(f, "{}", parts.join("·"))
+    }
+}
@@ -1,13 +1,16 @@
 //! Length, Distance, Radii etc.
-use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}};
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}, str::FromStr};
 use paste::paste;
 
 use serde::{Deserialize, Serialize};
 
+#[allow(clippy::module_inception)]
 pub mod iau;
 mod megastruct;
+mod vector;
 pub use megastruct::{Megastructure, SpatialContained};
-use crate::{DefoAble, MetricsInternalType, Squared, defo, iau::*, ratio};
+pub use vector::SpatialVector;
+use crate::{Area, DefoAble, MetricsInternalType, Squared, UnitTable, defo, units::UnitFactor, parse::{ParseMetricError, UnitRow, parse_metric}, format::{FormatMetric, Symbols, display_via_format}};
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy)]
 pub enum SpatialUnit {
@@ -43,107 +46,28 @@ pub trait AsCelestialRadii {
     fn ro(&self) -> SpatialUnit;
 }
 
-impl AsSpatialUnit for SpatialUnit {
-    fn m(&self) -> SpatialUnit {
-        match self {
-            Self::M(_) => *self,
-            Self::RE(v) => Self::M(*v * R_EARTH_METERS),
-            Self::RO(v) => Self::M(*v * R_SUN_METERS),
-            Self::Au(v) => Self::M(*v * AU_METERS),
-            Self::Ly(v) => Self::M(*v * LY_METERS),
-            Self::Pc(v) => Self::M(*v * PARSEC_METERS),
-        }
-    }
-
-    fn au(&self) -> SpatialUnit {
-        match self {
-            Self::M(v) => Self::Au(ratio(*v, AU_METERS)),
-            Self::RE(v) => Self::Au(*v * ratio(R_EARTH_METERS, AU_METERS)),
-            Self::RO(v) => Self::Au(*v * ratio(R_SUN_METERS, AU_METERS)),
-            Self::Au(_) => *self,
-            Self::Ly(v) => Self::Au(*v * ratio(LY_METERS, AU_METERS)),
-            Self::Pc(v) => Self::Au(*v * ratio(PARSEC_METERS, AU_METERS)),
-        }
-    }
-
-    fn ly(&self) -> SpatialUnit {
-        match self {
-            Self::M(v) => Self::Ly(ratio(*v, LY_METERS)),
-            Self::RE(v) => Self::Ly(*v * ratio(R_EARTH_METERS, LY_METERS)),
-            Self::RO(v) => Self::Ly(*v * ratio(R_SUN_METERS, LY_METERS)),
-            Self::Au(v) => Self::Ly(*v * ratio(AU_METERS, LY_METERS)),
-            Self::Ly(_) => *self,
-            Self::Pc(v) => Self::Ly(*v * ratio(PARSEC_METERS, LY_METERS)),
-        }
-    }
-
-    fn pc(&self) -> SpatialUnit {
-        match self {
-            Self::M(v) => Self::Pc(*v / PARSEC_METERS),
-            Self::RE(v) => Self::Pc(*v * ratio(R_EARTH_METERS, PARSEC_METERS)),
-            Self::RO(v) => Self::Pc(*v * ratio(R_SUN_METERS, PARSEC_METERS)),
-            Self::Au(v) => Self::Pc(*v * ratio(AU_METERS, PARSEC_METERS)),
-            Self::Ly(v) => Self::Pc(*v * ratio(LY_METERS, PARSEC_METERS)),
-            Self::Pc(_) => *self,
-        }
-    }
+impl UnitTable for SpatialUnit {
+    const SI_UNIT: &'static str = "m";
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::M(0.0), |_| 1.0),
+        (Self::RE(0.0), |cs| cs.r_earth.value),
+        (Self::RO(0.0), |cs| cs.r_sun.value),
+        (Self::Au(0.0), |cs| cs.au.value),
+        (Self::Ly(0.0), |cs| cs.ly.value),
+        (Self::Pc(0.0), |cs| cs.pc.value),
+    ];
 }
 
-impl AsCelestialRadii for SpatialUnit {
-    fn re(&self) -> SpatialUnit {
-        match self {
-            Self::M(v) => Self::RE(ratio(*v, R_EARTH_METERS)),
-            Self::RE(_) => *self,
-            Self::RO(v) => Self::RE(*v * ratio(R_SUN_METERS, R_EARTH_METERS)),
-            Self::Au(v) => Self::RE(*v * ratio(AU_METERS, R_EARTH_METERS)),
-            Self::Ly(v) => Self::RE(*v * ratio(LY_METERS, R_EARTH_METERS)),
-            Self::Pc(v) => Self::RE(*v * ratio(PARSEC_METERS, R_EARTH_METERS)),
-        }
-    }
-
-    fn ro(&self) -> SpatialUnit {
-        match self {
-            Self::M(v) => Self::RO(ratio(*v, R_SUN_METERS)),
-            Self::RE(v) => Self::RO(*v * ratio(R_EARTH_METERS, R_SUN_METERS)),
-            Self::RO(_) => *self,
-            Self::Au(v) => Self::RO(*v * ratio(AU_METERS, R_SUN_METERS)),
-            Self::Ly(v) => Self::RO(*v * ratio(LY_METERS, R_SUN_METERS)),
-            Self::Pc(v) => Self::RO(*v * ratio(PARSEC_METERS, R_SUN_METERS)),
-        }
-    }
+impl AsSpatialUnit for SpatialUnit {
+    fn m(&self) -> SpatialUnit { self.to_unit(&Self::M(0.0)) }
+    fn au(&self) -> SpatialUnit { self.to_unit(&Self::Au(0.0)) }
+    fn ly(&self) -> SpatialUnit { self.to_unit(&Self::Ly(0.0)) }
+    fn pc(&self) -> SpatialUnit { self.to_unit(&Self::Pc(0.0)) }
 }
 
-impl SpatialUnit {
-    /// Elevates the lower-magnitude mass of the two into higher-magnitude one.
-    fn unify(&self, other: &Self) -> (Self, Self) {
-        let rank = |su:&SpatialUnit| match su {
-            Self::M(_) => 1,
-            Self::RE(_) => 2,
-            Self::RO(_) => 3,
-            Self::Au(_) => 4,
-            Self::Ly(_) => 5,
-            Self::Pc(_) => 6,
-        };
-
-        match rank(self).cmp(&rank(other)) {
-            Ordering::Greater => {
-                let other_c = match self {
-                    Self::M(_) => other.m(),
-                    Self::RE(_) => other.re(),
-                    Self::RO(_) => other.ro(),
-                    Self::Au(_) => other.au(),
-                    Self::Ly(_) => other.ly(),
-                    Self::Pc(_) => other.pc(),
-                };
-                (self.clone(), other_c)
-            },
-            Ordering::Less => {
-                let self_c = self.cnv_into(other);
-                (self_c, other.clone())
-            },
-            Ordering::Equal => (self.clone(), other.clone())
-        }
-    }
+impl AsCelestialRadii for SpatialUnit {
+    fn re(&self) -> SpatialUnit { self.to_unit(&Self::RE(0.0)) }
+    fn ro(&self) -> SpatialUnit { self.to_unit(&Self::RO(0.0)) }
 }
 
 impl DefoAble for SpatialUnit {
@@ -170,14 +94,7 @@ impl DefoAble for SpatialUnit {
     }
 
     fn cnv_into(&self, other: &Self) -> Self {
-        match other {
-            Self::M(_) => self.m(),
-            Self::RE(_) => self.re(),
-            Self::RO(_) => self.ro(),
-            Self::Au(_) => self.au(),
-            Self::Ly(_) => self.ly(),
-            Self::Pc(_) => self.pc(),
-        }
+        self.to_unit(other)
     }
 }
 
@@ -189,31 +106,15 @@ impl PartialEq for SpatialUnit {
 
 impl PartialOrd for SpatialUnit {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        let (lhs, rhs) = self.unify(other);
-        match (lhs, rhs) {
-            (SpatialUnit::M(a), SpatialUnit::M(b))   |
-            (SpatialUnit::RE(a), SpatialUnit::RE(b)) |
-            (SpatialUnit::RO(a), SpatialUnit::RO(b)) |
-            (SpatialUnit::Au(a), SpatialUnit::Au(b)) |
-            (SpatialUnit::Ly(a), SpatialUnit::Ly(b)) |
-            (SpatialUnit::Pc(a), SpatialUnit::Pc(b)) => a.total_cmp(&b).into(),
-            _ => unreachable!("unify() unified already")
-        }
+        Some(self.cmp_in_larger_unit(other))
     }
 }
 
 impl Squared for SpatialUnit {
-    fn sq(&self) -> Self {
-        match self {
-            // More or less commonly used ones:
-            Self::M(v) => Self::M(v * v),
-            Self::Ly(v) => Self::Ly(v * v),
-            Self::Pc(v) => Self::Pc(v * v),
-            // The variants below exist just for completeness' sake…
-            Self::RE(v) => Self::RE(v * v),
-            Self::RO(v) => Self::RO(v * v),
-            Self::Au(v) => Self::Au(v * v),
-        }
+    type Output = Area;
+    /// Length × length is an [Area], not another length.
+    fn sq(&self) -> Area {
+        *self * *self
     }
 }
 
@@ -267,6 +168,55 @@ define_asspatial_for_prim!(f [32, 64, 128]);
 define_asspatial_for_prim!(8, 16, 32, 64, 128, size);
 defo!(SpatialUnit; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
 
+impl FormatMetric for SpatialUnit {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::M(_), _) => "m",
+            (Self::Au(_), _) => "au",
+            (Self::Ly(_), _) => "ly",
+            (Self::Pc(_), _) => "pc",
+            (Self::RE(_), Symbols::Unicode) => "R⊕",
+            (Self::RE(_), Symbols::Ascii) => "Rearth",
+            (Self::RO(_), Symbols::Unicode) => "R☉",
+            (Self::RO(_), Symbols::Ascii) => "Rsun",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::M(_) => 1,
+            Self::Au(_) => 3,
+            Self::Ly(_) |
+            Self::Pc(_) |
+            Self::RE(_) |
+            Self::RO(_) => 2,
+        }
+    }
+}
+
+display_via_format!(SpatialUnit);
+
+/// Accepted unit symbols, Unicode and ASCII alike.
+const SPATIAL_UNITS: &[UnitRow<SpatialUnit>] = &[
+    ("m", SpatialUnit::M),
+    ("km", |v| SpatialUnit::M(v * 1_000.0)),
+    ("au", SpatialUnit::Au), ("AU", SpatialUnit::Au), ("ua", SpatialUnit::Au),
+    ("ly", SpatialUnit::Ly),
+    ("pc", SpatialUnit::Pc),
+    ("R⊕", SpatialUnit::RE), ("RE", SpatialUnit::RE), ("Rearth", SpatialUnit::RE), ("R_earth", SpatialUnit::RE),
+    ("R☉", SpatialUnit::RO), ("R⊙", SpatialUnit::RO), ("RO", SpatialUnit::RO), ("Rsun", SpatialUnit::RO), ("R_sun", SpatialUnit::RO),
+];
+
+impl FromStr for SpatialUnit {
+    type Err = ParseMetricError;
+    /// Parse e.g. "3 ly", "12 R⊕", "1.3pc", "1 AU".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, SPATIAL_UNITS)
+    }
+}
+
 #[cfg(test)]
 mod spatial_tests {
     use super::*;
@@ -285,4 +235,30 @@ mod spatial_tests {
         assert!(a < 2);
         assert!(2 > a);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn squaring_gives_an_area() {
+        use crate::{ApproxEq, AsArea, Tolerance};
+        assert!(1.au().sq().approx_eq(&1.au2(), Tolerance::default()));
+        assert!(2.ly().sq().approx_eq(&(2.ly() * 2.ly()), Tolerance::default()));
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(Ok(SpatialUnit::Ly(3.0)), "3 ly".parse());
+        assert_eq!(Ok(SpatialUnit::RE(12.0)), "12 R⊕".parse());
+        assert_eq!(Ok(SpatialUnit::RO(1.0)), " 1Rsun ".parse());
+        assert_eq!(Ok(SpatialUnit::Au(1.5e-2)), "1.5E-2 AU".parse());
+        assert_eq!(Ok(1500.m()), "1.5 km".parse());
+        assert!("ly".parse::<SpatialUnit>().is_err());
+    }
+
+    #[test]
+    fn formatting() {
+        assert_eq!("4.20 ly", 4.2.ly().to_string());
+        assert_eq!("1.000 R⊕", format!("{:.3}", 1.re()));
+        assert_eq!("1.5e11 m", format!("{:.1e}", 1.au().m()));
+        assert_eq!("1.00 Rsun", format!("{:#}", 1.ro()));
+        assert_eq!(Ok(1.ro()), format!("{:#}", 1.ro()).parse());
+    }
+}
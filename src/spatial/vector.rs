@@ -0,0 +1,222 @@
+//! 3D positions/vectors with [SpatialUnit] components.
+use std::{fmt::Display, ops::{Add, Div, Mul, Neg, Sub}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Angle, Area, AsAngle, DefoAble, MetricsInternalType, SpatialUnit};
+
+/// A Cartesian vector whose components share one [SpatialUnit].
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct SpatialVector {
+    x: SpatialUnit,
+    y: SpatialUnit,
+    z: SpatialUnit,
+}
+
+impl SpatialVector {
+    /// A vector of `x`, `y` and `z`, with `y` and `z` converted into `x`'s unit.
+    pub fn new(x: SpatialUnit, y: SpatialUnit, z: SpatialUnit) -> Self {
+        Self { x, y: y.cnv_into(&x), z: z.cnv_into(&x) }
+    }
+
+    /// Zero vector in `unit`'s unit.
+    pub fn zero(unit: SpatialUnit) -> Self {
+        Self::from_raw([0.0; 3], unit)
+    }
+
+    /// From raw components in `unit`'s unit.
+    pub(crate) fn from_raw(v: [MetricsInternalType; 3], unit: SpatialUnit) -> Self {
+        let [x, y, z] = v.map(|c| {
+            let mut s = unit;
+            s.set(c);
+            s
+        });
+        Self { x, y, z }
+    }
+
+    /// Raw components, in [SpatialVector::unit]'s unit.
+    pub(crate) fn raw(&self) -> [MetricsInternalType; 3] {
+        [self.x.raw(), self.y.raw(), self.z.raw()]
+    }
+
+    /// From spherical `lon`, `lat` and `r`.
+    pub fn from_spherical(lon: Angle, lat: Angle, r: SpatialUnit) -> Self {
+        let (cb, sb) = (lat.cos(), lat.sin());
+        Self::from_raw([r.raw() * cb * lon.cos(), r.raw() * cb * lon.sin(), r.raw() * sb], r)
+    }
+
+    /// Spherical `(lon, lat, r)`, the angles in degrees and `lon` ∈ [0°, 360°).
+    pub fn to_spherical(&self) -> (Angle, Angle, SpatialUnit) {
+        let [x, y, z] = self.raw();
+        let lon = Angle::atan2(y, x).deg().normalized();
+        let lat = Angle::atan2(z, x.hypot(y)).deg();
+        (lon, lat, self.norm())
+    }
+
+    /// X-component.
+    pub fn x(&self) -> SpatialUnit { self.x }
+    /// Y-component.
+    pub fn y(&self) -> SpatialUnit { self.y }
+    /// Z-component.
+    pub fn z(&self) -> SpatialUnit { self.z }
+    /// The components as an array.
+    pub fn components(&self) -> [SpatialUnit; 3] { [self.x, self.y, self.z] }
+
+    /// Length of the vector.
+    pub fn norm(&self) -> SpatialUnit {
+        let [x, y, z] = self.raw();
+        let mut n = self.x;
+        n.set((x * x + y * y + z * z).sqrt());
+        n
+    }
+
+    /// Distance between two positions.
+    pub fn distance_to(&self, other: &Self) -> SpatialUnit {
+        (*other - *self).norm()
+    }
+
+    /// Direction of `self` as a dimensionless unit vector; `None` for a zero vector.
+    pub fn direction(&self) -> Option<[MetricsInternalType; 3]> {
+        let n = self.norm().raw();
+        (n != 0.0 && n.is_finite()).then(|| self.raw().map(|c| c / n))
+    }
+
+    /// Dot product.
+    pub fn dot(&self, other: &Self) -> Area {
+        let [a, b, c] = self.m().raw();
+        let [d, e, f] = other.m().raw();
+        Area::M2(a * d + b * e + c * f)
+    }
+
+    /// Cross product; its components are areas.
+    pub fn cross(&self, other: &Self) -> [Area; 3] {
+        let [a, b, c] = self.m().raw();
+        let [d, e, f] = other.m().raw();
+        [Area::M2(b * f - c * e), Area::M2(c * d - a * f), Area::M2(a * e - b * d)]
+    }
+
+    /// `self` in `unit`'s unit.
+    pub fn to_unit(&self, unit: &SpatialUnit) -> Self {
+        Self { x: self.x.cnv_into(unit), y: self.y.cnv_into(unit), z: self.z.cnv_into(unit) }
+    }
+}
+
+// The [AsSpatialUnit]/[AsCelestialRadii] conversions, vector-wise. These traits produce a [SpatialUnit],
+// and giving them an associated output type would break type inference on `1.au()` and friends.
+impl SpatialVector {
+    /// self → meters
+    pub fn m(&self) -> SpatialVector { self.to_unit(&SpatialUnit::M(0.0)) }
+    /// self → au
+    pub fn au(&self) -> SpatialVector { self.to_unit(&SpatialUnit::Au(0.0)) }
+    /// self → ly
+    pub fn ly(&self) -> SpatialVector { self.to_unit(&SpatialUnit::Ly(0.0)) }
+    /// self → parsec
+    pub fn pc(&self) -> SpatialVector { self.to_unit(&SpatialUnit::Pc(0.0)) }
+    /// self → Earth radii
+    pub fn re(&self) -> SpatialVector { self.to_unit(&SpatialUnit::RE(0.0)) }
+    /// self → Solar radii
+    pub fn ro(&self) -> SpatialVector { self.to_unit(&SpatialUnit::RO(0.0)) }
+}
+
+impl Add for SpatialVector {
+    type Output = Self;
+    /// Component-wise, in `self`'s unit.
+    fn add(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.raw(), rhs.to_unit(&self.x).raw());
+        Self::from_raw([a[0] + b[0], a[1] + b[1], a[2] + b[2]], self.x)
+    }
+}
+
+impl Sub for SpatialVector {
+    type Output = Self;
+    /// Component-wise, in `self`'s unit.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Neg for SpatialVector {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        self * -1.0
+    }
+}
+
+impl Mul<MetricsInternalType> for SpatialVector {
+    type Output = Self;
+    fn mul(self, rhs: MetricsInternalType) -> Self::Output {
+        Self::from_raw(self.raw().map(|c| c * rhs), self.x)
+    }
+}
+
+impl Mul<SpatialVector> for MetricsInternalType {
+    type Output = SpatialVector;
+    fn mul(self, rhs: SpatialVector) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Div<MetricsInternalType> for SpatialVector {
+    type Output = Self;
+    fn div(self, rhs: MetricsInternalType) -> Self::Output {
+        Self::from_raw(self.raw().map(|c| c / rhs), self.x)
+    }
+}
+
+impl Display for SpatialVector {
+    /// E.g. "(1.000 au, 0.000 au, -2.000 au)"; format options are passed on to the components.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(")?;
+        for (i, c) in self.components().iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            Display::fmt(c, f)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[cfg(test)]
+mod vector_tests {
+    use crate::{ApproxEq, AsArea, AsSpatialUnit, Tolerance, assert_quantity_eq};
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = SpatialVector::new(1.au(), 2.au(), 2.au());
+        assert_eq!(3.au(), a.norm());
+        let b = SpatialVector::new(1.au().m(), 0.m(), 0.m());
+        let c = a + b;
+        assert_eq!(SpatialVector::new(2.au(), 2.au(), 2.au()), c);
+        assert!(matches!(c.x(), SpatialUnit::Au(_)));
+        assert_eq!(a, c - b);
+        assert_eq!(SpatialVector::new(2.au(), 4.au(), 4.au()), a * 2.0);
+        assert_eq!(a * 2.0, 2.0 * a);
+        assert_eq!(a, a * 2.0 / 2.0);
+        assert_eq!(2.au(), a.distance_to(&SpatialVector::new(1.au(), 2.au(), 0.au())));
+    }
+
+    #[test]
+    fn products() {
+        let x = SpatialVector::new(1.m(), 0.m(), 0.m());
+        let y = SpatialVector::new(0.m(), 1.m(), 0.m());
+        assert_eq!(0.m2(), x.dot(&y));
+        assert_eq!([0.m2(), 0.m2(), 1.m2()], x.cross(&y));
+        let au = SpatialVector::new(1.au(), 0.au(), 0.au());
+        assert_quantity_eq!(1.au2(), au.dot(&au), Tolerance::relative(1e-12));
+    }
+
+    #[test]
+    fn conversions() {
+        let v = SpatialVector::from_spherical(30.deg(), 45.deg(), 2.pc());
+        let (lon, lat, r) = v.to_spherical();
+        assert!(lon.approx_eq(&30.deg(), Tolerance::absolute(1e-12)));
+        assert!(lat.approx_eq(&45.deg(), Tolerance::absolute(1e-12)));
+        assert_quantity_eq!(2.pc(), r);
+        assert_quantity_eq!(2.pc(), v.au().norm());
+        assert!(matches!(v.ly().z(), SpatialUnit::Ly(_)));
+        assert_eq!("(1.000 au, 0.000 au, 0.000 au)", SpatialVector::new(1.au(), 0.au(), 0.au()).to_string());
+        assert!(SpatialVector::zero(1.m()).direction().is_none());
+    }
+}
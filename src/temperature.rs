@@ -2,15 +2,15 @@
 //! 
 //! Kelvin, Celsius, and the special cases of stellar remnants.
 use std::cmp::Ordering;
-use std::fmt::Display;
 use std::ops::{Add, Sub, Div, Mul};
+use std::str::FromStr;
 use paste::paste;
 use serde::{Deserialize, Serialize};
 
 mod k;
 pub use k::ABS_ZERO;
-use k::K_C_DELTA;
-use crate::{DefoAble, MetricsInternalType, Squared, defo};
+pub(crate) use k::K_C_DELTA;
+use crate::{DefoAble, MetricsInternalType, Squared, defo, parse::{ParseMetricError, ParseMetricErrorKind, UnitRow, parse_metric}, format::{FormatMetric, Symbols, display_via_format}};
 const K_NEUTRON: Temperature = Temperature::K(1e6);
 const K_WDWARF: Temperature = Temperature::K(1e5);
 
@@ -42,7 +42,7 @@ impl AsTemperature for Temperature {
     fn k(&self) -> Self {
         match self {
             Self::K(v) => Self::K(v.max(0.0)),
-            Self::C(v) => Self::K(*v - K_C_DELTA).k(),
+            Self::C(v) => Self::K(*v + K_C_DELTA).k(),
             Self::N => K_NEUTRON,
             Self::D => K_WDWARF,
             Self::X => Self::X,
@@ -53,9 +53,9 @@ impl AsTemperature for Temperature {
     fn c(&self) -> Temperature {
         match self {
             Self::C(v) => Self::C(v.max(-K_C_DELTA)),
-            Self::K(v) => Self::C(v.max(0.0) + K_C_DELTA),
-            Self::N => K_NEUTRON - K_C_DELTA,
-            Self::D => K_WDWARF - K_C_DELTA,
+            Self::K(v) => Self::C(v.max(0.0) - K_C_DELTA),
+            Self::N => Self::C(K_NEUTRON.raw() - K_C_DELTA),
+            Self::D => Self::C(K_WDWARF.raw() - K_C_DELTA),
             Self::X => Self::X
         }
     }
@@ -63,6 +63,7 @@ impl AsTemperature for Temperature {
 
 impl Temperature {
     /// self → `f64`.
+    #[allow(clippy::unnecessary_cast)]// not so unnecessary with f128…
     pub fn as_f64(&self) -> f64 {
         let v = self.raw();
         #[cfg(feature = "f128_stable")]{
@@ -151,12 +152,13 @@ impl PartialEq for Temperature {
             (Self::D, x) |
             (x, Self::D) => x.k().eq(&K_WDWARF),
             (Self::C(a), Self::C(b)) |
-            (Self::K(a), Self::K(b)) => a.total_cmp(&b) == Ordering::Equal,
+            (Self::K(a), Self::K(b)) => a.total_cmp(b) == Ordering::Equal,
             (Self::K(a), Self::C(b)) |
-            (Self::C(b), Self::K(a)) => a.total_cmp(&(b - K_C_DELTA)) == Ordering::Equal
+            (Self::C(b), Self::K(a)) => a.total_cmp(&(b + K_C_DELTA)) == Ordering::Equal
         }
     }
 
+    #[allow(clippy::partialeq_ne_impl)]// see above, X is neither eq() nor ne().
     fn ne(&self, other: &Self) -> bool {
         match (self, other) {
             // Black holes can't be compared…
@@ -182,26 +184,39 @@ impl PartialOrd for Temperature {
             (Self::D, x) |
             (x, Self::D) => x.k().raw().total_cmp(&K_WDWARF.raw()).into(),
             (Self::C(a), Self::C(b)) |
-            (Self::K(a), Self::K(b)) => a.total_cmp(&b).into(),
-            (Self::C(c), Self::K(k)) => (*c - K_C_DELTA).total_cmp(&k).into(),
-            (Self::K(k), Self::C(c)) => k.total_cmp(&(*c - K_C_DELTA)).into()
+            (Self::K(a), Self::K(b)) => a.total_cmp(b).into(),
+            (Self::C(c), Self::K(k)) => (*c + K_C_DELTA).total_cmp(k).into(),
+            (Self::K(k), Self::C(c)) => k.total_cmp(&(*c + K_C_DELTA)).into()
         }
     }
 }
 
-impl Display for Temperature {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl FormatMetric for Temperature {
+    fn format_value(&self) -> Option<MetricsInternalType> {
         match self {
-            Self::X => write!(f, "\u{221e}K"),
-            Self::N => write!(f, "{}", K_NEUTRON),
-            Self::D => write!(f, "{}", K_WDWARF),
-            Self::K(v) => write!(f, "{:.1}K", v),
-            Self::C(v) => write!(f, "{:.1}⁰C", v)
+            Self::X => None,
+            _ => Some(self.raw())
         }
     }
+
+    fn format_symbol(&self, symbols: Symbols) -> &'static str {
+        match (self, symbols) {
+            (Self::X, Symbols::Unicode) => "\u{221e}K",
+            (Self::X, Symbols::Ascii) => "inf K",
+            (Self::C(_), Symbols::Unicode) => "⁰C",
+            (Self::C(_), Symbols::Ascii) => "degC",
+            _ => "K"
+        }
+    }
+
+    fn default_decimals(&self) -> usize { 1 }
+    fn default_spaced(&self) -> bool { false }
 }
 
+display_via_format!(Temperature);
+
 impl Squared for Temperature {
+    type Output = Self;
     /// Self squared…
     /// 
     /// Note that squaring temperature values is *usually* utterly meaningless, but it is useful in some equations.
@@ -217,6 +232,41 @@ impl Squared for Temperature {
     }
 }
 
+/// Accepted unit symbols.
+const TEMPERATURE_UNITS: &[UnitRow<Temperature>] = &[
+    ("K", Temperature::K), ("\u{212a}", Temperature::K),
+    ("C", Temperature::C), ("°C", Temperature::C), ("⁰C", Temperature::C), ("℃", Temperature::C), ("degC", Temperature::C),
+];
+
+/// Stellar remnants, which come without a number.
+const REMNANT_TOKENS: &[(&str, Temperature)] = &[
+    ("D", Temperature::D), ("WD", Temperature::D),
+    ("N", Temperature::N), ("NS", Temperature::N),
+    ("X", Temperature::X), ("BH", Temperature::X), ("\u{221e}K", Temperature::X), ("\u{221e}", Temperature::X), ("inf K", Temperature::X),
+];
+
+impl FromStr for Temperature {
+    type Err = ParseMetricError;
+    /// Parse e.g. "5778 K", "-40 °C", or one of the remnant tokens ("D", "N", "X", …).
+    /// Anything colder than absolute zero is [ParseMetricErrorKind::OutOfRange].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = s.trim();
+        if let Some((_, t)) = REMNANT_TOKENS.iter().find(|(sym, _)| sym.eq_ignore_ascii_case(token)) {
+            return Ok(*t);
+        }
+        let t = parse_metric(s, TEMPERATURE_UNITS)?;
+        let below_abs_zero = match t {
+            Self::K(v) => v < 0.0,
+            Self::C(v) => v < -K_C_DELTA,
+            _ => false
+        };
+        match below_abs_zero {
+            true => Err(ParseMetricError::new(ParseMetricErrorKind::OutOfRange, s.len() - s.trim_start().len())),
+            false => Ok(t)
+        }
+    }
+}
+
 macro_rules! define_from_prim_temperature {
     (f [$($bits:tt),+]) => {$(define_from_prim_temperature!(@f $bits);)*};
     // f128 special case - drop when f128 is stable enough (and/or hardwarewise useable).
@@ -268,5 +318,57 @@ mod temperature_tests {
         assert!(a > b);
         assert_ne!(a, b);
         let c = a / 2.0;
+        assert_eq!(50.k(), c);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parsing() {
+        use crate::Temperature;
+        assert_eq!(Ok(5778.k()), "5778 K".parse());
+        assert_eq!(Ok(Temperature::C(-40.0)), "-40 °C".parse());
+        assert_eq!(Ok(Temperature::C(20.0)), 20.c().to_string().parse());
+        assert!(matches!("wd".parse(), Ok(Temperature::D)));
+        assert!(matches!("X".parse(), Ok(Temperature::X)));
+        assert!(matches!(Temperature::X.to_string().parse(), Ok(Temperature::X)));
+        assert!("5778 F".parse::<Temperature>().is_err());
+    }
+
+    #[test]
+    fn parsing_below_absolute_zero() {
+        use crate::{ParseMetricErrorKind, Temperature};
+        let e = " -5 K".parse::<Temperature>().unwrap_err();
+        assert_eq!((&ParseMetricErrorKind::OutOfRange, 1), (e.kind(), e.position()));
+        let e = "-273.16 °C".parse::<Temperature>().unwrap_err();
+        assert_eq!((&ParseMetricErrorKind::OutOfRange, 0), (e.kind(), e.position()));
+        assert_eq!(Ok(Temperature::C(-273.15)), "-273.15 °C".parse());
+        assert_eq!(Ok(Temperature::K(0.0)), "0 K".parse());
+    }
+
+    #[test]
+    fn formatting() {
+        use crate::{Temperature, format::{FormatMetric, FormatOptions, Spacing}};
+        assert_eq!("5778.0K", 5778.k().to_string());
+        assert_eq!("100000.0K", Temperature::D.to_string());
+        assert_eq!("5.778e3K", format!("{:e}", 5778.k()));
+        assert_eq!("20degC", format!("{:#.0}", 20.c()));
+        assert_eq!("inf K", format!("{:#}", Temperature::X));
+        assert_eq!("5800 K", 5778.k().format_with(FormatOptions::default().significant(2).spacing(Spacing::Spaced)));
+    }
+
+    /// Regression: the baseline converted with the wrong sign of K_C_DELTA (0 °C came out as -273.15 K, and
+    /// N/D `.c()` returned Kelvin variants).
+    #[test]
+    fn celsius_kelvin_sign() {
+        use crate::{DefoAble, Temperature};
+        assert_eq!(Temperature::K(373.15), 100.c().k());
+        assert_eq!(Temperature::K(273.15), 0.c().k());
+        assert!((0.k().c().raw() + 273.15).abs() < 1e-12);
+        assert!((300.k().c().raw() - 26.85).abs() < 1e-12);
+        assert_eq!(0.c(), 273.15.k());
+        assert_eq!(273.15.k(), 0.c());
+        assert!(0.c() < 274.k() && 274.k() > 0.c());
+        assert!(0.c() > 273.k() && 273.k() < 0.c());
+        assert!(matches!(Temperature::D.c(), Temperature::C(v) if (v - (1e5 - 273.15)).abs() < 1e-9));
+        assert!(matches!(Temperature::N.c(), Temperature::C(v) if (v - (1e6 - 273.15)).abs() < 1e-9));
+    }
+}
@@ -0,0 +1,214 @@
+//! Time
+//! 
+//! Seconds, days, and years - Julian, sidereal, tropical, mega- and giga-.
+use std::{cmp::Ordering, ops::{Add, Div, Mul, Sub}, str::FromStr};
+
+use paste::paste;
+use serde::{Deserialize, Serialize};
+
+use crate::{AsSpatialUnit, ConstantSet, DefoAble, MetricsInternalType, SpatialUnit, UnitTable, defo, define_as_for_prim, format::{FormatMetric, Symbols, display_via_format}, parse::{ParseMetricError, UnitRow, parse_metric}, units::UnitFactor};
+
+/// Seconds per day.
+pub const DAY_SECONDS: MetricsInternalType = 86_400.0;
+/// Days per Julian year.
+pub const JULIAN_YEAR_DAYS: MetricsInternalType = 365.25;
+/// Days per sidereal year (J2000).
+pub const SIDEREAL_YEAR_DAYS: MetricsInternalType = 365.256_363_004;
+/// Days per mean tropical year (J2000).
+pub const TROPICAL_YEAR_DAYS: MetricsInternalType = 365.242_190_402;
+/// Seconds per Julian year.
+pub const JULIAN_YEAR_SECONDS: MetricsInternalType = JULIAN_YEAR_DAYS * DAY_SECONDS;
+
+/// Some time "magnitudes".
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub enum Time {
+    /// Seconds (SI).
+    S(MetricsInternalType),
+    /// Days, 86400 s.
+    D(MetricsInternalType),
+    /// Julian years, 365.25 d.
+    Yr(MetricsInternalType),
+    /// Sidereal years, ~365.256 d.
+    SiderealYr(MetricsInternalType),
+    /// Mean tropical years, ~365.242 d.
+    TropicalYr(MetricsInternalType),
+    /// Megayears (Julian).
+    Myr(MetricsInternalType),
+    /// Gigayears (Julian).
+    Gyr(MetricsInternalType),
+}
+
+/// Trait for converting `self` to some specific [Time]-type.
+pub trait AsTime {
+    /// self → s
+    fn s(&self) -> Time;
+    /// self → d
+    fn d(&self) -> Time;
+    /// self → Julian yr
+    fn yr(&self) -> Time;
+    /// self → sidereal yr
+    fn sidereal_yr(&self) -> Time;
+    /// self → tropical yr
+    fn tropical_yr(&self) -> Time;
+    /// self → Myr
+    fn myr(&self) -> Time;
+    /// self → Gyr
+    fn gyr(&self) -> Time;
+}
+
+impl UnitTable for Time {
+    const SI_UNIT: &'static str = "s";
+    // NOTE: tropical (365.242 d) < Julian (365.25 d) < sidereal (365.256 d), thus the order.
+    const UNITS: &'static [UnitFactor<Self>] = &[
+        (Self::S(0.0), |_| 1.0),
+        (Self::D(0.0), |_| DAY_SECONDS),
+        (Self::TropicalYr(0.0), |_| TROPICAL_YEAR_DAYS * DAY_SECONDS),
+        (Self::Yr(0.0), |_| JULIAN_YEAR_SECONDS),
+        (Self::SiderealYr(0.0), |_| SIDEREAL_YEAR_DAYS * DAY_SECONDS),
+        (Self::Myr(0.0), |_| 1e6 * JULIAN_YEAR_SECONDS),
+        (Self::Gyr(0.0), |_| 1e9 * JULIAN_YEAR_SECONDS),
+    ];
+}
+
+impl AsTime for Time {
+    fn s(&self) -> Time { self.to_unit(&Self::S(0.0)) }
+    fn d(&self) -> Time { self.to_unit(&Self::D(0.0)) }
+    fn yr(&self) -> Time { self.to_unit(&Self::Yr(0.0)) }
+    fn sidereal_yr(&self) -> Time { self.to_unit(&Self::SiderealYr(0.0)) }
+    fn tropical_yr(&self) -> Time { self.to_unit(&Self::TropicalYr(0.0)) }
+    fn myr(&self) -> Time { self.to_unit(&Self::Myr(0.0)) }
+    fn gyr(&self) -> Time { self.to_unit(&Self::Gyr(0.0)) }
+}
+
+impl DefoAble for Time {
+    fn raw(&self) -> MetricsInternalType {
+        match self {
+            Self::S(v)          |
+            Self::D(v)          |
+            Self::Yr(v)         |
+            Self::SiderealYr(v) |
+            Self::TropicalYr(v) |
+            Self::Myr(v)        |
+            Self::Gyr(v) => *v
+        }
+    }
+
+    fn set(&mut self, value: MetricsInternalType) {
+        match self {
+            Self::S(v)          |
+            Self::D(v)          |
+            Self::Yr(v)         |
+            Self::SiderealYr(v) |
+            Self::TropicalYr(v) |
+            Self::Myr(v)        |
+            Self::Gyr(v) => *v = value
+        }
+    }
+
+    fn cnv_into(&self, other: &Self) -> Self {
+        self.to_unit(other)
+    }
+}
+
+impl PartialEq for Time {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_in_larger_unit(other))
+    }
+}
+
+impl FormatMetric for Time {
+    fn format_value(&self) -> Option<MetricsInternalType> { Some(self.raw()) }
+
+    fn format_symbol(&self, _: Symbols) -> &'static str {
+        match self {
+            Self::S(_) => "s",
+            Self::D(_) => "d",
+            Self::Yr(_) => "yr",
+            Self::SiderealYr(_) => "yr(sid)",
+            Self::TropicalYr(_) => "yr(trop)",
+            Self::Myr(_) => "Myr",
+            Self::Gyr(_) => "Gyr",
+        }
+    }
+
+    fn default_decimals(&self) -> usize {
+        match self {
+            Self::S(_) => 1,
+            _ => 2
+        }
+    }
+}
+
+display_via_format!(Time);
+
+/// Accepted unit symbols.
+const TIME_UNITS: &[UnitRow<Time>] = &[
+    ("s", Time::S), ("sec", Time::S),
+    ("d", Time::D), ("day", Time::D), ("days", Time::D),
+    ("yr", Time::Yr), ("a", Time::Yr), ("y", Time::Yr), ("year", Time::Yr), ("years", Time::Yr),
+    ("yr(sid)", Time::SiderealYr),
+    ("yr(trop)", Time::TropicalYr),
+    ("Myr", Time::Myr), ("Ma", Time::Myr),
+    ("Gyr", Time::Gyr), ("Ga", Time::Gyr),
+];
+
+impl FromStr for Time {
+    type Err = ParseMetricError;
+    /// Parse e.g. "3.2 d", "4.6 Gyr", "1 yr".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_metric(s, TIME_UNITS)
+    }
+}
+
+impl SpatialUnit {
+    /// Light-travel time across `self`.
+    pub fn light_time(&self) -> Time {
+        Time::S(self.m().raw() / ConstantSet::DEFAULT.c.value)
+    }
+}
+
+impl Time {
+    /// Distance light travels in `self`, as light-years.
+    pub fn light_distance(&self) -> SpatialUnit {
+        SpatialUnit::M(self.s().raw() * ConstantSet::DEFAULT.c.value).ly()
+    }
+}
+
+define_as_for_prim!(AsTime => Time [s => S, d => D, yr => Yr, sidereal_yr => SiderealYr, tropical_yr => TropicalYr, myr => Myr, gyr => Gyr]; f [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+defo!(Time; float [32, 64, 128], int [8, 16, 32, 64, 128, size]);
+
+#[cfg(test)]
+mod time_tests {
+    use crate::{ApproxEq, Tolerance, assert_quantity_eq};
+    use super::*;
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Time::S(JULIAN_YEAR_SECONDS), 1.yr().s());
+        assert_eq!(1.yr(), 365.25.d());
+        assert!(1.sidereal_yr() > 1.yr());
+        assert!(1.tropical_yr() < 1.yr());
+        assert_quantity_eq!(4.6.gyr(), 4600.myr());
+    }
+
+    #[test]
+    fn light() {
+        assert_eq!(1.yr(), 1.ly().light_time().yr());
+        assert!(1.au().light_time().s().approx_eq(&499.004784.s(), Tolerance::absolute(1e-6)));
+        assert_eq!(1.ly(), 1.yr().light_distance());
+    }
+
+    #[test]
+    fn parsing_and_display() {
+        assert_eq!(Ok(4.6.gyr()), "4.6 Gyr".parse());
+        assert_eq!(Ok(3.2.d()), "3.2d".parse());
+        assert_eq!("1.00 yr(sid)", 1.sidereal_yr().to_string());
+        assert_eq!(Ok(1.sidereal_yr()), 1.sidereal_yr().to_string().parse());
+    }
+}
@@ -0,0 +1,152 @@
+//! Table-driven unit conversions.
+//! 
+//! Each (linear) metric has one table of its units and their sizes in canonical SI units - all the
+//! unit pairs are derived from that. Adding a unit = adding a row (and its `As…` fn).
+use std::{cmp::Ordering, mem::discriminant};
+
+use crate::{ConstantSet, DefoAble, MetricsInternalType};
+
+/// A unit table row: a unit "tag" (value irrelevant) and its size in canonical SI units per [ConstantSet].
+pub type UnitFactor<T> = (T, fn(&ConstantSet) -> MetricsInternalType);
+
+/// A metric whose units are all plain multiples of a canonical SI unit.
+pub trait UnitTable: DefoAble + Copy + 'static {
+    /// Name of the canonical SI unit, e.g. `"kg"`.
+    const SI_UNIT: &'static str;
+    /// One row per unit, smallest unit first; the row index doubles as the unit's rank.
+    const UNITS: &'static [UnitFactor<Self>];
+
+    /// Table row index of `self`'s unit.
+    fn rank(&self) -> usize {
+        Self::UNITS.iter()
+            .position(|(u, _)| discriminant(u) == discriminant(self))
+            .expect("every unit has its row in the table")
+    }
+
+    /// Size of `self`'s unit in canonical SI units, per [ConstantSet::DEFAULT].
+    fn si_factor(&self) -> MetricsInternalType {
+        self.si_factor_with(&ConstantSet::DEFAULT)
+    }
+
+    /// Size of `self`'s unit in canonical SI units, per given constant set.
+    fn si_factor_with(&self, constants: &ConstantSet) -> MetricsInternalType {
+        (Self::UNITS[self.rank()].1)(constants)
+    }
+
+    /// `self`'s value in canonical SI units.
+    fn to_si(&self) -> MetricsInternalType {
+        self.raw() * self.si_factor()
+    }
+
+    /// `self` expressed in `unit`'s unit (`unit`'s value is irrelevant), per [ConstantSet::DEFAULT].
+    fn to_unit(&self, unit: &Self) -> Self {
+        self.to_unit_with(unit, &ConstantSet::DEFAULT)
+    }
+
+    /// `self` expressed in `unit`'s unit (`unit`'s value is irrelevant), per given constant set.
+    fn to_unit_with(&self, unit: &Self, constants: &ConstantSet) -> Self {
+        if discriminant(self) == discriminant(unit) {
+            return *self;
+        }
+        let mut u = *unit;
+        u.set(self.raw() * self.si_factor_with(constants) / unit.si_factor_with(constants));
+        u
+    }
+
+    /// Compare `self` and `other` in the larger of their two units, so that `a` vs. `b` agrees with `b` vs. `a`.
+    fn cmp_in_larger_unit(&self, other: &Self) -> Ordering {
+        let (s, o) = match self.rank().cmp(&other.rank()) {
+            Ordering::Less => (self.to_unit(other), *other),
+            _ => (*self, other.to_unit(self))
+        };
+        s.raw().total_cmp(&o.raw())
+    }
+}
+
+#[cfg(test)]
+mod units_tests {
+    use crate::{Acceleration, Angle, ApproxEq, Area, AsMass, AsSpatialUnit, Density, Luminosity, Mass, SpatialUnit, Time, Tolerance, Velocity, Volume};
+    use super::*;
+
+    /// Every unit → every other unit and back again, and against the SI value.
+    fn round_trip_matrix<T: UnitTable + ApproxEq + std::fmt::Debug>(values: &[MetricsInternalType]) {
+        for (from, _) in T::UNITS {
+            for v in values {
+                let mut x = *from;
+                x.set(*v);
+                for (to, _) in T::UNITS {
+                    let y = x.to_unit(to);
+                    assert_eq!(discriminant(to), discriminant(&y));
+                    assert!(y.to_si().approx_eq(&x.to_si(), Tolerance::default()), "{x:?} → {y:?}");
+                    assert!(y.to_unit(from).approx_eq(&x, Tolerance::default()), "{x:?} → {y:?} → back");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let values = [0.0, 1.0, -2.5, 1e-9, 3.3e21];
+        round_trip_matrix::<Mass>(&values);
+        round_trip_matrix::<SpatialUnit>(&values);
+        round_trip_matrix::<Area>(&values);
+        round_trip_matrix::<Volume>(&values);
+        round_trip_matrix::<Density>(&values);
+        round_trip_matrix::<Time>(&values);
+        round_trip_matrix::<Velocity>(&values);
+        round_trip_matrix::<Acceleration>(&values);
+        round_trip_matrix::<Luminosity>(&values);
+        round_trip_matrix::<Angle>(&values);
+    }
+
+    #[test]
+    fn comparison_is_symmetric() {
+        fn check<T: UnitTable + std::fmt::Debug>() {
+            for (a, _) in T::UNITS {
+                for (b, _) in T::UNITS {
+                    for i in 1..200 {
+                        let mut x = *a;
+                        x.set(i as MetricsInternalType * 1e-3 + 1e-4);
+                        let y = x.to_unit(b);
+                        assert_eq!(x.cmp_in_larger_unit(&y), y.cmp_in_larger_unit(&x).reverse(), "{x:?} vs. {y:?}");
+                    }
+                }
+            }
+        }
+        check::<Mass>();
+        check::<SpatialUnit>();
+        check::<Area>();
+        check::<Volume>();
+        check::<Density>();
+        check::<Time>();
+        check::<Velocity>();
+        check::<Acceleration>();
+        check::<Luminosity>();
+        check::<Angle>();
+    }
+
+    #[test]
+    fn known_values() {
+        assert!(1.mj().kg().approx_eq(&1.898187e27.kg(), Tolerance::relative(1e-6)));
+        assert!(1.898187e27.kg().mj().approx_eq(&1.mj(), Tolerance::relative(1e-6)));
+        assert!(1.mo().g().approx_eq(&1.988475e33.g(), Tolerance::relative(1e-6)));
+        assert!(1.988475e33.g().mo().approx_eq(&1.mo(), Tolerance::relative(1e-6)));
+        assert_eq!(SpatialUnit::M(149_597_870_700.0), 1.au().m());
+    }
+
+    #[test]
+    fn constant_sets() {
+        let m_2009 = 1.mo().to_unit_with(&Mass::Kg(0.0), &ConstantSet::IAU_2009);
+        assert!(m_2009 != 1.mo().kg());
+        assert_eq!(ConstantSet::IAU_2009.m_sun.value, m_2009.raw());
+        assert_eq!(Mass::MO(1.0), m_2009.to_unit_with(&Mass::MO(0.0), &ConstantSet::IAU_2009));
+        // Light-year is exact, and the same in all the sets.
+        assert_eq!(9_460_730_472_580_800.0, 1.ly().m().raw());
+    }
+
+    #[test]
+    fn identity_is_exact() {
+        assert!(matches!(0.1.mo().mo(), Mass::MO(v) if v == 0.1));
+        assert!(matches!(0.1.pc().pc(), SpatialUnit::Pc(v) if v == 0.1));
+    }
+}